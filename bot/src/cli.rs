@@ -1,6 +1,9 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
+use crate::llm::LlmApi;
 use crate::platforms::cli::{BlueskyCliArgs, MastodonCliArgs, XCliArgs};
+use crate::translator::TranslatorKind;
 
 #[derive(Parser, Debug)]
 #[command(version, about = "Social media posting bot.", long_about = None)]
@@ -25,6 +28,61 @@ pub struct CliArgs {
     #[arg(short = 's', long, default_value_t = 120)]
     pub post_pause_time: u64,
 
+    /// The ISO 639-1 code of the language used for the posts whose language wasn't detected.
+    #[arg(short = 'l', long, default_value = "ro")]
+    pub default_language: String,
+
+    /// The base URL of an OpenAI or Ollama compatible server summarizing the posts before publishing,
+    /// e.g. https://api.openai.com/v1 or http://localhost:11434. The posts are not summarized without it.
+    #[arg(long, requires = "summarizer_model")]
+    pub summarizer_url: Option<String>,
+
+    /// The API of the summarization server.
+    #[arg(long, value_enum, default_value_t = LlmApi::OpenAi)]
+    pub summarizer_api: LlmApi,
+
+    /// The model summarizing the posts.
+    #[arg(long)]
+    pub summarizer_model: Option<String>,
+
+    /// The API key of the summarization server.
+    #[arg(long)]
+    pub summarizer_api_key: Option<String>,
+
+    /// A file with the summarization prompt template.
+    /// Its {title}, {text}, {language} and {max_chars} placeholders are replaced with the post's.
+    #[arg(long)]
+    pub summarizer_prompt_file: Option<PathBuf>,
+
+    /// The time in seconds after which a summarization request is abandoned, the post keeps its summary.
+    #[arg(long, default_value_t = 30)]
+    pub summarizer_timeout_seconds: u64,
+
+    /// The ISO 639-1 code of the language the posts are translated to before publishing, e.g. en.
+    /// The posts are not translated without it.
+    #[arg(long, requires = "translator_url")]
+    pub translate_to: Option<String>,
+
+    /// The translation backend.
+    #[arg(long, value_enum, default_value_t = TranslatorKind::LibreTranslate)]
+    pub translator: TranslatorKind,
+
+    /// The base URL of the translation server, e.g. http://localhost:5000 for LibreTranslate.
+    #[arg(long)]
+    pub translator_url: Option<String>,
+
+    /// The model translating the posts, required by the LLM backends.
+    #[arg(long)]
+    pub translator_model: Option<String>,
+
+    /// The API key of the translation server.
+    #[arg(long)]
+    pub translator_api_key: Option<String>,
+
+    /// The time in seconds after which a translation request is abandoned, the post is published untranslated.
+    #[arg(long, default_value_t = 30)]
+    pub translator_timeout_seconds: u64,
+
     /// Platform
     #[command(subcommand)]
     pub platform: Command,
@@ -0,0 +1,124 @@
+use anyhow::anyhow;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The HTTP API spoken by the LLM server.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum LlmApi {
+    /// The OpenAI chat completions API, `{url}/chat/completions`.
+    #[value(name = "openai")]
+    OpenAi,
+    /// The Ollama generate API, `{url}/api/generate`.
+    Ollama,
+}
+
+#[derive(Serialize, Debug)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Serialize, Debug)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChatCompletionMessage {
+    content: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChatCompletionChoice {
+    message: ChatCompletionMessage,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Serialize, Debug)]
+struct OllamaGenerateRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    stream: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct OllamaGenerateResponse {
+    response: String,
+}
+
+/// A client of an OpenAI or Ollama compatible LLM server.
+pub struct LlmClient {
+    client: reqwest::Client,
+    api: LlmApi,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl LlmClient {
+    /// Creates a new client for the LLM server at the given base URL, requests are abandoned after `timeout`.
+    pub fn new(api: LlmApi, url: &str, model: &str, timeout: Duration) -> Self {
+        LlmClient {
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .expect("Failed to build HTTP client"),
+            api,
+            url: url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            api_key: None,
+        }
+    }
+
+    /// Sets the API key sent as a bearer token.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key;
+        self
+    }
+
+    /// Sends the prompt to the LLM and returns its reply.
+    pub async fn complete(&self, prompt: &str) -> Result<String, anyhow::Error> {
+        let request = match self.api {
+            LlmApi::OpenAi => self
+                .client
+                .post(format!("{}/chat/completions", self.url))
+                .json(&ChatCompletionRequest {
+                    model: &self.model,
+                    messages: vec![ChatMessage {
+                        role: "user",
+                        content: prompt,
+                    }],
+                }),
+            LlmApi::Ollama => self.client.post(format!("{}/api/generate", self.url)).json(
+                &OllamaGenerateRequest {
+                    model: &self.model,
+                    prompt,
+                    stream: false,
+                },
+            ),
+        };
+        let request = match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        };
+
+        let response = request.send().await?.error_for_status()?;
+        match self.api {
+            LlmApi::OpenAi => response
+                .json::<ChatCompletionResponse>()
+                .await?
+                .choices
+                .into_iter()
+                .next()
+                .map(|choice| choice.message.content)
+                .ok_or_else(|| anyhow!("the completion has no choices")),
+            LlmApi::Ollama => Ok(response.json::<OllamaGenerateResponse>().await?.response),
+        }
+    }
+}
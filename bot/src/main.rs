@@ -1,4 +1,8 @@
 use crate::cli::{CliArgs, Command};
+use crate::llm::LlmClient;
+use crate::summarizer::Summarizer;
+use crate::translator::{LibreTranslate, LlmTranslator, Translator, TranslatorBackend};
+use anyhow::Context;
 use clap::Parser;
 use infrastructure::RedisService;
 use log::{error, info, warn};
@@ -9,7 +13,10 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::{thread, time};
 
 mod cli;
+mod llm;
 mod platforms;
+mod summarizer;
+mod translator;
 
 //noinspection DuplicatedCode
 /// Sets up a signal handler in a separate thread to handle SIGINT and SIGTERM signals.
@@ -46,6 +53,47 @@ async fn main() -> Result<(), anyhow::Error> {
         warn!("Failed to create consumer group and stream: {}", err);
     }
 
+    let translator = match (&args.translate_to, &args.translator_url) {
+        (Some(target_language), Some(url)) => {
+            let timeout = time::Duration::from_secs(args.translator_timeout_seconds);
+            let api_key = args.translator_api_key.clone();
+            let backend: Box<dyn TranslatorBackend> = match args.translator.llm_api() {
+                Some(api) => {
+                    let model = args
+                        .translator_model
+                        .as_deref()
+                        .context("--translator-model is required by the LLM translators")?;
+                    let llm = LlmClient::new(api, url, model, timeout).with_api_key(api_key);
+                    Box::new(LlmTranslator::new(llm))
+                }
+                None => Box::new(LibreTranslate::new(url, timeout).with_api_key(api_key)),
+            };
+            Some(Translator::new(backend, target_language).with_store(redis_service.clone()))
+        }
+        _ => None,
+    };
+
+    let summarizer = match (&args.summarizer_url, &args.summarizer_model) {
+        (Some(url), Some(model)) => {
+            let llm = LlmClient::new(
+                args.summarizer_api,
+                url,
+                model,
+                time::Duration::from_secs(args.summarizer_timeout_seconds),
+            )
+            .with_api_key(args.summarizer_api_key.clone());
+            let mut summarizer = Summarizer::new(llm);
+            if let Some(path) = &args.summarizer_prompt_file {
+                let template = std::fs::read_to_string(path).with_context(|| {
+                    format!("failed to read the prompt file {}", path.display())
+                })?;
+                summarizer = summarizer.with_prompt_template(template);
+            }
+            Some(summarizer)
+        }
+        _ => None,
+    };
+
     let mut publisher_client: Box<dyn Publisher> = match args.platform {
         Command::Bluesky(bluesky) => {
             Box::new(BlueSkyClient::new(&bluesky.bluesky_handle, &bluesky.bluesky_password).await?)
@@ -69,7 +117,18 @@ async fn main() -> Result<(), anyhow::Error> {
             )
             .await
         {
-            Ok(post) => {
+            Ok(mut post) => {
+                if post.language.is_none() {
+                    post.language = Some(args.default_language.clone());
+                }
+                // The summary is written in the language of the translated post.
+                if let Some(translator) = &translator {
+                    translator.translate_post(&mut post).await;
+                }
+                if let Some(summarizer) = &summarizer {
+                    let max_chars = publisher_client.max_summary_chars(&post);
+                    summarizer.summarize_post(&mut post, max_chars).await;
+                }
                 match publisher_client.publish_post(post.clone()).await {
                     Ok(_) => {}
                     Err(_) => {
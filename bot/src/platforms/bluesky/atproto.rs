@@ -2,6 +2,9 @@ use chrono::{DateTime, Local, Utc};
 use post::NewsPost;
 use serde::{Deserialize, Serialize};
 
+/// The maximum number of characters of a Bluesky post.
+const MAX_POST_CHARS: usize = 300;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct BlobLinkRef {
     #[serde(rename = "$link")]
@@ -74,6 +77,44 @@ impl ATprotoRepoCreateRecordEmbed {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct FacetIndex {
+    #[serde(rename(serialize = "byteStart", deserialize = "byteStart"))]
+    pub byte_start: usize,
+    #[serde(rename(serialize = "byteEnd", deserialize = "byteEnd"))]
+    pub byte_end: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct FacetFeature {
+    #[serde(rename(serialize = "$type", deserialize = "$type"))]
+    pub feature_type: String,
+    pub tag: String,
+}
+
+/// A facet annotates a range of the post's text, given in UTF-8 bytes.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Facet {
+    pub index: FacetIndex,
+    pub features: Vec<FacetFeature>,
+}
+
+impl Facet {
+    /// Creates a facet making the text between the byte offsets a hashtag, the tag is given without the `#`.
+    fn tag(byte_start: usize, byte_end: usize, tag: &str) -> Self {
+        Facet {
+            index: FacetIndex {
+                byte_start,
+                byte_end,
+            },
+            features: vec![FacetFeature {
+                feature_type: "app.bsky.richtext.facet#tag".to_string(),
+                tag: tag.to_string(),
+            }],
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ATprotoRepoCreateRecordRecord {
     pub text: String,
@@ -83,16 +124,50 @@ pub struct ATprotoRepoCreateRecordRecord {
     pub langs: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub embed: Option<ATprotoRepoCreateRecordEmbed>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub facets: Vec<Facet>,
 }
 
 impl ATprotoRepoCreateRecordRecord {
-    fn new(text: &str, date: DateTime<Utc>, embed: Option<ATprotoRepoCreateRecordEmbed>) -> Self {
+    fn new(
+        text: &str,
+        date: DateTime<Utc>,
+        langs: Vec<String>,
+        embed: Option<ATprotoRepoCreateRecordEmbed>,
+    ) -> Self {
         ATprotoRepoCreateRecordRecord {
             text: text.to_string(),
             created_at: date.to_rfc3339(),
-            langs: vec![String::from("ro-RO")],
+            langs,
             embed,
+            facets: vec![],
+        }
+    }
+
+    /// Appends the hashtags which fit in the post's length to the text, with the facets making them clickable.
+    fn with_hashtags(mut self, tags: &[String]) -> Self {
+        let separator = if self.text.is_empty() { "" } else { "\n\n" };
+        let max_chars =
+            MAX_POST_CHARS.saturating_sub(self.text.chars().count() + separator.chars().count());
+        let hashtags = post::format_hashtags(tags, max_chars);
+        if hashtags.is_empty() {
+            return self;
+        }
+
+        self.text.push_str(separator);
+        for (i, hashtag) in hashtags.split(' ').enumerate() {
+            if i > 0 {
+                self.text.push(' ');
+            }
+            let byte_start = self.text.len();
+            self.text.push_str(hashtag);
+            self.facets.push(Facet::tag(
+                byte_start,
+                self.text.len(),
+                hashtag.trim_start_matches('#'),
+            ));
         }
+        self
     }
 }
 #[derive(Serialize, Deserialize, Debug)]
@@ -115,13 +190,16 @@ impl ATProtoRepoCreateRecord {
 impl From<NewsPost> for ATProtoRepoCreateRecord {
     fn from(post: NewsPost) -> Self {
         let dt = Local::now();
-        let dt_utc = DateTime::<Utc>::from_naive_utc_and_offset(dt.naive_utc(), Utc);
+        let now = DateTime::<Utc>::from_naive_utc_and_offset(dt.naive_utc(), Utc);
+        // Use the article's publication time, unless it's in the future.
+        let dt_utc = post.published_at.filter(|p| *p <= now).unwrap_or(now);
 
         ATProtoRepoCreateRecord::new(
             "",
             ATprotoRepoCreateRecordRecord::new(
                 post.title.clone().unwrap_or(String::from("")).as_str(),
                 dt_utc,
+                post.language.clone().into_iter().collect(),
                 Some(ATprotoRepoCreateRecordEmbed::new(
                     post.link.unwrap_or_default().as_str(),
                     post.title.unwrap_or_default().as_str(),
@@ -133,7 +211,8 @@ impl From<NewsPost> for ATProtoRepoCreateRecord {
                         .unwrap_or(post.summary.unwrap_or_default().as_str()),
                     None,
                 )),
-            ),
+            )
+            .with_hashtags(&post.tags),
         )
     }
 }
@@ -141,7 +220,7 @@ impl From<NewsPost> for ATProtoRepoCreateRecord {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDateTime;
+    use chrono::{NaiveDateTime, TimeZone};
 
     #[test]
     fn test_atproto_server_create_session_serialization() -> Result<(), anyhow::Error> {
@@ -168,6 +247,7 @@ mod tests {
             ATprotoRepoCreateRecordRecord::new(
                 "some post",
                 DateTime::from_naive_utc_and_offset(naive_datetime, Utc),
+                vec![String::from("ro-RO")],
                 None,
             ),
         );
@@ -192,6 +272,7 @@ mod tests {
             ATprotoRepoCreateRecordRecord::new(
                 "some post",
                 DateTime::from_naive_utc_and_offset(naive_datetime, Utc),
+                vec![String::from("ro-RO")],
                 Some(ATprotoRepoCreateRecordEmbed::new(
                     "https://some-news.ro/some",
                     "Some very important news",
@@ -222,6 +303,7 @@ mod tests {
             ATprotoRepoCreateRecordRecord::new(
                 "some post",
                 DateTime::from_naive_utc_and_offset(naive_datetime, Utc),
+                vec![String::from("ro-RO")],
                 Some(ATprotoRepoCreateRecordEmbed::new(
                     "https://some-news.ro/some",
                     "Some very important news",
@@ -245,6 +327,63 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_atproto_repo_create_record_from_post_uses_published_at() {
+        let published_at = Utc.with_ymd_and_hms(2024, 12, 30, 13, 45, 0).unwrap();
+        let post = NewsPost {
+            title: Some(String::from("Some very important news")),
+            link: Some(String::from("https://some-news.ro/some")),
+            published_at: Some(published_at),
+            ..Default::default()
+        };
+
+        let record: ATProtoRepoCreateRecord = post.clone().into();
+        assert_eq!(record.record.created_at, "2024-12-30T13:45:00+00:00");
+        assert!(record.record.langs.is_empty());
+
+        let future_post = NewsPost {
+            published_at: Some(Utc::now() + chrono::TimeDelta::days(1)),
+            language: Some(String::from("en")),
+            ..post
+        };
+        let record: ATProtoRepoCreateRecord = future_post.into();
+        let created_at = DateTime::parse_from_rfc3339(&record.record.created_at).unwrap();
+        assert!(created_at <= Utc::now());
+        assert_eq!(record.record.langs, vec![String::from("en")]);
+    }
+
+    #[test]
+    fn test_atproto_repo_create_record_from_post_with_hashtags() -> Result<(), anyhow::Error> {
+        let post = NewsPost {
+            title: Some(String::from("Știri")),
+            link: Some(String::from("https://some-news.ro/stiri")),
+            tags: vec![String::from("Guvern"), String::from("Economie")],
+            ..Default::default()
+        };
+
+        let record: ATProtoRepoCreateRecord = post.clone().into();
+
+        assert_eq!(record.record.text, "Știri\n\n#Guvern #Economie");
+        assert_eq!(
+            serde_json::to_string(&record.record.facets)?,
+            r##"[{"index":{"byteStart":8,"byteEnd":15},"features":[{"$type":"app.bsky.richtext.facet#tag","tag":"Guvern"}]},{"index":{"byteStart":16,"byteEnd":25},"features":[{"$type":"app.bsky.richtext.facet#tag","tag":"Economie"}]}]"##
+        );
+
+        // The hashtags which don't fit are dropped.
+        let long_post = NewsPost {
+            title: Some("a".repeat(290)),
+            ..post
+        };
+        let record: ATProtoRepoCreateRecord = long_post.into();
+        assert_eq!(
+            record.record.text,
+            format!("{}\n\n#Guvern", "a".repeat(290))
+        );
+        assert_eq!(record.record.facets.len(), 1);
+
+        Ok(())
+    }
+
     #[test]
     fn test_blob_new_serialization() -> Result<(), anyhow::Error> {
         let blob = Blob::new("asa", "image/jpeg", 1);
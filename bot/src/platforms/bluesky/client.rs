@@ -91,10 +91,11 @@ impl BlueSkyClient {
         Ok(())
     }
 
-    /// Uploads an image.
+    /// Uploads an image, a JPEG unless its MIME type is known.
     pub async fn upload_image_by_url(
         &mut self,
         image_url: &str,
+        mime_type: Option<&str>,
     ) -> Result<BlobResponse, anyhow::Error> {
         let data: Vec<u8> = self
             .client
@@ -109,7 +110,7 @@ impl BlueSkyClient {
         Ok(self
             .client
             .post("https://bsky.social/xrpc/com.atproto.repo.uploadBlob")
-            .header("Content-Type", "image/jpeg")
+            .header("Content-Type", mime_type.unwrap_or("image/jpeg"))
             .header(
                 "Authorization",
                 format!("Bearer {}", self.auth_token.access_jwt),
@@ -127,9 +128,10 @@ impl BlueSkyClient {
 async fn add_image_to_post(
     client: &mut BlueSkyClient,
     image_url: &str,
+    mime_type: Option<&str>,
     record: &mut atproto::ATProtoRepoCreateRecord,
 ) -> Result<(), anyhow::Error> {
-    let thumb = client.upload_image_by_url(image_url).await?;
+    let thumb = client.upload_image_by_url(image_url, mime_type).await?;
     record.record.embed.as_mut().unwrap().external.thumb = Some(thumb.blob);
 
     Ok(())
@@ -142,7 +144,8 @@ impl Publisher for BlueSkyClient {
         data.repo = self.user_handle.clone();
 
         if let Some(image_link) = post.image.clone() {
-            let result = add_image_to_post(self, &image_link, &mut data).await;
+            let mime_type = post.image_info.as_ref().map(|i| i.mime_type.as_str());
+            let result = add_image_to_post(self, &image_link, mime_type, &mut data).await;
             if let Err(err) = result {
                 warn!("Failed to upload image: {err}")
             }
@@ -164,4 +167,9 @@ impl Publisher for BlueSkyClient {
             }
         }
     }
+
+    /// The summary is the description of the link card.
+    fn max_summary_chars(&self, _post: &NewsPost) -> usize {
+        300
+    }
 }
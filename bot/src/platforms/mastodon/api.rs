@@ -16,18 +16,21 @@ pub struct PartialMediaResponse {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PostStatusRequest {
     pub status: String,
-    pub language: String,
+    /// The ISO 639-1 code of the status' language, Mastodon detects it when missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
     pub visibility: String,
     pub media_ids: Vec<String>,
 }
 
 impl From<NewsPost> for PostStatusRequest {
     fn from(value: NewsPost) -> Self {
+        let language = value.language.clone();
         let status = post::extract_text_from_post(value, 500);
 
         PostStatusRequest {
             status,
-            language: String::from("ro"),
+            language,
             visibility: String::from("public"),
             media_ids: vec![],
         }
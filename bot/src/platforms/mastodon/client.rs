@@ -55,10 +55,11 @@ impl MastodonClient {
         Ok(response.json().await?)
     }
 
-    /// Uploads an image to Mastodon.
+    /// Uploads an image to Mastodon, a JPEG unless its MIME type is known.
     pub async fn upload_media_by_url(
         &mut self,
         image_url: &str,
+        mime_type: Option<&str>,
     ) -> Result<PartialMediaResponse, anyhow::Error> {
         let data: Vec<u8> = self
             .client
@@ -70,9 +71,11 @@ impl MastodonClient {
             .await?
             .to_vec();
 
+        let mime_type = mime_type.unwrap_or("image/jpeg");
+        let extension = mime_type.strip_prefix("image/").unwrap_or("jpeg");
         let file_part = reqwest::multipart::Part::bytes(data)
-            .file_name("image.jpg")
-            .mime_str("image/jpg")?;
+            .file_name(format!("image.{extension}"))
+            .mime_str(mime_type)?;
 
         let form = reqwest::multipart::Form::new().part("file", file_part);
 
@@ -96,7 +99,10 @@ impl Publisher for MastodonClient {
         // Step1: Upload image to Mastodon
         let media_response = if post.image.is_some() {
             let response = self
-                .upload_media_by_url(post.image.clone().unwrap().as_str())
+                .upload_media_by_url(
+                    post.image.clone().unwrap().as_str(),
+                    post.image_info.as_ref().map(|i| i.mime_type.as_str()),
+                )
                 .await;
 
             match response {
@@ -129,4 +135,9 @@ impl Publisher for MastodonClient {
             }
         }
     }
+
+    /// The summary shares the toot's 500 characters with the title and the link.
+    fn max_summary_chars(&self, post: &NewsPost) -> usize {
+        post::summary_budget(post, 500)
+    }
 }
@@ -177,4 +177,9 @@ impl Publisher for XApiClient {
 
         self.post_tweet(post_text, media_id).await
     }
+
+    /// The summary shares the tweet's 280 characters with the title and the link.
+    fn max_summary_chars(&self, post: &NewsPost) -> usize {
+        post::summary_budget(post, 280)
+    }
 }
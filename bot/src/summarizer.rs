@@ -0,0 +1,249 @@
+use crate::llm::LlmClient;
+use anyhow::anyhow;
+use log::{debug, warn};
+use post::NewsPost;
+
+/// The prompt used when no template is configured.
+pub const DEFAULT_PROMPT_TEMPLATE: &str = "Summarize the following news article for a social media post \
+in the language with the ISO 639-1 code {language}, in at most {max_chars} characters. \
+Reply with the summary only, without quotes, hashtags or links.\n\nTitle: {title}\n\n{text}";
+
+/// Renders the prompt template, replacing the `{title}`, `{text}`, `{language}` and `{max_chars}` placeholders.
+pub fn render_prompt(
+    template: &str,
+    title: &str,
+    text: &str,
+    language: &str,
+    max_chars: usize,
+) -> String {
+    template
+        .replace("{title}", title)
+        .replace("{language}", language)
+        .replace("{max_chars}", &max_chars.to_string())
+        .replace("{text}", text)
+}
+
+/// The summarizer asks an LLM to write a short summary of a post, sized to the target platform.
+pub struct Summarizer {
+    llm: LlmClient,
+    prompt_template: String,
+}
+
+impl Summarizer {
+    /// Creates a new summarizer using the default prompt.
+    pub fn new(llm: LlmClient) -> Self {
+        Summarizer {
+            llm,
+            prompt_template: String::from(DEFAULT_PROMPT_TEMPLATE),
+        }
+    }
+
+    /// Sets the prompt template, see [render_prompt] for its placeholders.
+    pub fn with_prompt_template(mut self, prompt_template: String) -> Self {
+        self.prompt_template = prompt_template;
+        self
+    }
+
+    /// Returns a summary of the post's body, or of its summary when it has no body,
+    /// in the post's language and in at most `max_chars` characters.
+    pub async fn summarize(
+        &self,
+        post: &NewsPost,
+        max_chars: usize,
+    ) -> Result<String, anyhow::Error> {
+        let text = post
+            .body
+            .as_deref()
+            .or(post.summary.as_deref())
+            .ok_or_else(|| anyhow!("the post has no text to summarize"))?;
+        let prompt = render_prompt(
+            &self.prompt_template,
+            post.title.as_deref().unwrap_or_default(),
+            text,
+            post.language.as_deref().unwrap_or_default(),
+            max_chars,
+        );
+
+        let reply = self.llm.complete(&prompt).await?;
+        let summary = post::normalize_text(reply.trim().trim_matches('"'));
+        if summary.is_empty() {
+            return Err(anyhow!("the summary is empty"));
+        }
+        Ok(post::truncate_text(&summary, max_chars))
+    }
+
+    /// Replaces the post's summary with the LLM's one, the original summary is kept if summarizing fails
+    /// or if there's no room left for a summary.
+    pub async fn summarize_post(&self, post: &mut NewsPost, max_chars: usize) {
+        if max_chars == 0 {
+            debug!("No room for the summary of post {:?}", post.link);
+            return;
+        }
+        match self.summarize(post, max_chars).await {
+            Ok(summary) => {
+                debug!("Summarized post {:?}: {summary}", post.link);
+                post.summary = Some(summary);
+            }
+            Err(err) => warn!(
+                "Failed to summarize post {:?}, keeping its summary: {err:#}",
+                post.link
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::LlmApi;
+    use serde_json::json;
+    use std::time::Duration;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn post(body: Option<&str>) -> NewsPost {
+        NewsPost {
+            title: Some(String::from("Titlu")),
+            summary: Some(String::from("Rezumatul original")),
+            link: Some(String::from("https://some-news.ro/titlu")),
+            language: Some(String::from("ro")),
+            body: body.map(|b| b.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render_prompt() {
+        assert_eq!(
+            render_prompt(
+                "{title} in {language}, {max_chars} chars: {text}",
+                "Titlu",
+                "Textul {title}",
+                "ro",
+                280
+            ),
+            "Titlu in ro, 280 chars: Textul {title}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_summarize_openai() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("Authorization", "Bearer secret"))
+            .and(body_partial_json(json!({
+                "model": "gpt-4o-mini",
+                "messages": [{"role": "user", "content": "Titlu (ro, 20): Corpul articolului"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "choices": [{"message": {"role": "assistant", "content": " \"Un rezumat mult prea lung\" "}}]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let summarizer = Summarizer::new(
+            LlmClient::new(
+                LlmApi::OpenAi,
+                &format!("{}/v1/", mock_server.uri()),
+                "gpt-4o-mini",
+                Duration::from_secs(5),
+            )
+            .with_api_key(Some(String::from("secret"))),
+        )
+        .with_prompt_template(String::from("{title} ({language}, {max_chars}): {text}"));
+
+        let mut post = post(Some("Corpul articolului"));
+        summarizer.summarize_post(&mut post, 20).await;
+
+        assert_eq!(post.summary, Some(String::from("Un rezumat mult…")));
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_summarize_ollama() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .and(body_partial_json(
+                json!({"model": "llama3.2", "stream": false}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"response": "Un rezumat scurt."})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let summarizer = Summarizer::new(LlmClient::new(
+            LlmApi::Ollama,
+            &mock_server.uri(),
+            "llama3.2",
+            Duration::from_secs(5),
+        ));
+
+        let mut post = post(None);
+        summarizer.summarize_post(&mut post, 300).await;
+
+        assert_eq!(post.summary, Some(String::from("Un rezumat scurt.")));
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_summarize_falls_back_to_the_original_summary() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"response": "Prea târziu."}))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        let slow = Summarizer::new(LlmClient::new(
+            LlmApi::Ollama,
+            &mock_server.uri(),
+            "llama3.2",
+            Duration::from_millis(100),
+        ));
+        let failing = Summarizer::new(LlmClient::new(
+            LlmApi::OpenAi,
+            &format!("{}/v1", mock_server.uri()),
+            "gpt-4o-mini",
+            Duration::from_secs(5),
+        ));
+
+        let mut post = post(Some("Corpul articolului"));
+        slow.summarize_post(&mut post, 300).await;
+        failing.summarize_post(&mut post, 300).await;
+
+        assert_eq!(post.summary, Some(String::from("Rezumatul original")));
+    }
+
+    #[tokio::test]
+    async fn test_summarize_without_room() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"response": "Rezumat"})))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        let summarizer = Summarizer::new(LlmClient::new(
+            LlmApi::Ollama,
+            &mock_server.uri(),
+            "llama3.2",
+            Duration::from_secs(5),
+        ));
+
+        let mut post = post(Some("Corpul articolului"));
+        summarizer.summarize_post(&mut post, 0).await;
+
+        assert_eq!(post.summary, Some(String::from("Rezumatul original")));
+        mock_server.verify().await;
+    }
+}
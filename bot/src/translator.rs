@@ -0,0 +1,387 @@
+use crate::llm::{LlmApi, LlmClient};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::ValueEnum;
+use infrastructure::RedisService;
+use log::{debug, warn};
+use post::NewsPost;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The time in seconds a translation is kept in Redis.
+const TRANSLATION_TTL: u64 = 60 * 60 * 24 * 30;
+
+/// The available translation backends.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum TranslatorKind {
+    /// A LibreTranslate compatible API, `{url}/translate`.
+    #[value(name = "libretranslate")]
+    LibreTranslate,
+    /// An LLM behind the OpenAI chat completions API.
+    #[value(name = "openai")]
+    OpenAi,
+    /// An LLM behind the Ollama generate API.
+    Ollama,
+}
+
+impl TranslatorKind {
+    /// Returns the API of the LLM backends.
+    pub fn llm_api(self) -> Option<LlmApi> {
+        match self {
+            TranslatorKind::LibreTranslate => None,
+            TranslatorKind::OpenAi => Some(LlmApi::OpenAi),
+            TranslatorKind::Ollama => Some(LlmApi::Ollama),
+        }
+    }
+}
+
+/// Translates texts between languages given by their ISO 639-1 codes.
+#[async_trait]
+pub trait TranslatorBackend: Send + Sync {
+    /// Translates the text to the target language, the source language is detected when it's `None`.
+    async fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<String, anyhow::Error>;
+}
+
+#[derive(Serialize, Debug)]
+struct LibreTranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LibreTranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+}
+
+/// Translates with a LibreTranslate compatible API.
+pub struct LibreTranslate {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl LibreTranslate {
+    /// Creates a new backend for the server at the given base URL, requests are abandoned after `timeout`.
+    pub fn new(url: &str, timeout: Duration) -> Self {
+        LibreTranslate {
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .expect("Failed to build HTTP client"),
+            url: url.trim_end_matches('/').to_string(),
+            api_key: None,
+        }
+    }
+
+    /// Sets the API key sent with every request.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key;
+        self
+    }
+}
+
+#[async_trait]
+impl TranslatorBackend for LibreTranslate {
+    async fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<String, anyhow::Error> {
+        let response: LibreTranslateResponse = self
+            .client
+            .post(format!("{}/translate", self.url))
+            .json(&LibreTranslateRequest {
+                q: text,
+                source: source.unwrap_or("auto"),
+                target,
+                format: "text",
+                api_key: self.api_key.as_deref(),
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response.translated_text)
+    }
+}
+
+/// Translates with an LLM.
+pub struct LlmTranslator {
+    llm: LlmClient,
+}
+
+impl LlmTranslator {
+    /// Creates a new backend prompting the LLM to translate.
+    pub fn new(llm: LlmClient) -> Self {
+        LlmTranslator { llm }
+    }
+}
+
+#[async_trait]
+impl TranslatorBackend for LlmTranslator {
+    async fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<String, anyhow::Error> {
+        let source = source
+            .map(|source| format!(" from the language with the ISO 639-1 code {source}"))
+            .unwrap_or_default();
+        let prompt = format!(
+            "Translate the following text{source} to the language with the ISO 639-1 code {target}. \
+             Reply with the translation only.\n\n{text}"
+        );
+        self.llm.complete(&prompt).await
+    }
+}
+
+/// Translates the posts to the target language, caching the translations by the hash of their text.
+/// Without a store the translations are cached in memory.
+pub struct Translator {
+    backend: Box<dyn TranslatorBackend>,
+    target_language: String,
+    translations: Mutex<HashMap<String, String>>,
+    store: Option<RedisService>,
+}
+
+impl Translator {
+    /// Creates a new translator to the language with the given ISO 639-1 code.
+    pub fn new(backend: Box<dyn TranslatorBackend>, target_language: &str) -> Self {
+        Translator {
+            backend,
+            target_language: target_language.to_string(),
+            translations: Mutex::new(HashMap::new()),
+            store: None,
+        }
+    }
+
+    /// Caches the translations in Redis so they survive restarts and are shared by the bots.
+    pub fn with_store(mut self, redis_service: RedisService) -> Self {
+        self.store = Some(redis_service);
+        self
+    }
+
+    /// Returns the cache key of the text's translation to the target language.
+    pub fn translation_key(target_language: &str, text: &str) -> String {
+        format!("bot:translation:{target_language}:{:x}", md5::compute(text))
+    }
+
+    /// Returns the cached translation of the text.
+    async fn cached_translation(&self, key: &str) -> Option<String> {
+        let Some(mut store) = self.store.clone() else {
+            return self.translations.lock().unwrap().get(key).cloned();
+        };
+        match store.get_value::<String>(key).await {
+            Ok(translation) => translation,
+            Err(err) => {
+                warn!("Failed to load the translation {key}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Caches the translation of the text.
+    async fn cache_translation(&self, key: &str, translation: &str) {
+        let Some(mut store) = self.store.clone() else {
+            self.translations
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), translation.to_string());
+            return;
+        };
+        if let Err(err) = store
+            .set_value(key, &translation, Some(TRANSLATION_TTL))
+            .await
+        {
+            warn!("Failed to save the translation {key}: {err}");
+        }
+    }
+
+    /// Translates the text from the source language to the target language.
+    async fn translate_text(
+        &self,
+        text: &str,
+        source: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        let key = Self::translation_key(&self.target_language, text);
+        if let Some(translation) = self.cached_translation(&key).await {
+            return Ok(translation);
+        }
+
+        let translation = self
+            .backend
+            .translate(text, source, &self.target_language)
+            .await?;
+        let translation = post::normalize_text(&translation);
+        if translation.is_empty() {
+            return Err(anyhow!("the translation is empty"));
+        }
+        self.cache_translation(&key, &translation).await;
+        Ok(translation)
+    }
+
+    /// Translates the post's title and summary and sets its language to the target language, its link is kept.
+    /// Posts already in the target language are unchanged, the post is unchanged if a translation fails.
+    pub async fn translate_post(&self, post: &mut NewsPost) {
+        if post.language.as_deref() == Some(self.target_language.as_str()) {
+            return;
+        }
+
+        let source = post.language.as_deref();
+        let translate = |text: Option<String>| async move {
+            match text {
+                Some(text) => self.translate_text(&text, source).await.map(Some),
+                None => Ok(None),
+            }
+        };
+        let result = async {
+            let title = translate(post.title.clone()).await?;
+            let summary = translate(post.summary.clone()).await?;
+            Ok::<_, anyhow::Error>((title, summary))
+        };
+        match result.await {
+            Ok((title, summary)) => {
+                debug!(
+                    "Translated post {:?} to {}",
+                    post.link, self.target_language
+                );
+                post.title = title;
+                post.summary = summary;
+                post.language = Some(self.target_language.clone());
+            }
+            Err(err) => warn!(
+                "Failed to translate post {:?} to {}, publishing it untranslated: {err:#}",
+                post.link, self.target_language
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn post(title: &str, language: &str) -> NewsPost {
+        NewsPost {
+            title: Some(title.to_string()),
+            summary: Some(String::from("Rezumat")),
+            link: Some(String::from("https://some-news.ro/stire")),
+            language: Some(language.to_string()),
+            ..Default::default()
+        }
+    }
+
+    async fn libre_translate(mock_server: &MockServer, q: &str, translated: &str) {
+        Mock::given(method("POST"))
+            .and(path("/translate"))
+            .and(body_partial_json(
+                json!({"q": q, "source": "ro", "target": "en", "format": "text"}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"translatedText": translated})),
+            )
+            .expect(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_translate_post_libre_translate() {
+        let mock_server = MockServer::start().await;
+        libre_translate(&mock_server, "Știre", "News").await;
+        libre_translate(&mock_server, "Altă știre", "Other news").await;
+        libre_translate(&mock_server, "Rezumat", "Summary").await;
+        let backend = LibreTranslate::new(&mock_server.uri(), Duration::from_secs(5));
+        let translator = Translator::new(Box::new(backend), "en");
+
+        let mut first = post("Știre", "ro");
+        let mut second = post("Altă știre", "ro");
+        translator.translate_post(&mut first).await;
+        translator.translate_post(&mut second).await;
+
+        assert_eq!(first.title, Some(String::from("News")));
+        assert_eq!(first.summary, Some(String::from("Summary")));
+        assert_eq!(first.language, Some(String::from("en")));
+        assert_eq!(first.link, Some(String::from("https://some-news.ro/stire")));
+        assert_eq!(second.title, Some(String::from("Other news")));
+        // The summary's translation is cached.
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_translate_post_llm() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"response": " News "})))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        let llm = LlmClient::new(
+            LlmApi::Ollama,
+            &mock_server.uri(),
+            "llama3.2",
+            Duration::from_secs(5),
+        );
+        let translator = Translator::new(Box::new(LlmTranslator::new(llm)), "en");
+
+        let mut post = post("Știre", "ro");
+        translator.translate_post(&mut post).await;
+
+        assert_eq!(post.title, Some(String::from("News")));
+        assert_eq!(post.language, Some(String::from("en")));
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_translate_post_keeps_untranslatable_posts() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/translate"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        let backend = LibreTranslate::new(&mock_server.uri(), Duration::from_secs(5));
+        let translator = Translator::new(Box::new(backend), "en");
+
+        let mut english = post("News", "en");
+        let mut failing = post("Știre", "ro");
+        translator.translate_post(&mut english).await;
+        translator.translate_post(&mut failing).await;
+
+        assert_eq!(english, post("News", "en"));
+        assert_eq!(failing, post("Știre", "ro"));
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_translation_key() {
+        assert_eq!(
+            Translator::translation_key("en", "Știre"),
+            Translator::translation_key("en", "Știre")
+        );
+        assert_ne!(
+            Translator::translation_key("en", "Știre"),
+            Translator::translation_key("fr", "Știre")
+        );
+        assert!(Translator::translation_key("en", "Știre").starts_with("bot:translation:en:"));
+    }
+}
@@ -4,8 +4,10 @@ use redis::Value::BulkString;
 use redis::aio::MultiplexedConnection;
 use redis::streams::StreamReadReply;
 use redis::{AsyncCommands, RedisError, RedisResult, Value};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+#[derive(Clone)]
 pub struct RedisService {
     multiplexed_connection: MultiplexedConnection,
 }
@@ -36,6 +38,44 @@ impl RedisService {
             .await;
     }
 
+    /// Returns the JSON value stored at the key, or `None` if the key doesn't exist.
+    pub async fn get_value<T>(&mut self, key: &str) -> Result<Option<T>, anyhow::Error>
+    where
+        T: DeserializeOwned,
+    {
+        let data: Option<String> = self.multiplexed_connection.get(key).await?;
+        match data {
+            Some(data) => Ok(Some(serde_json::from_str(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Stores the value as JSON at the key, the key expires after `ttl` seconds if given.
+    pub async fn set_value<T>(
+        &mut self,
+        key: &str,
+        value: &T,
+        ttl: Option<u64>,
+    ) -> Result<(), anyhow::Error>
+    where
+        T: Serialize,
+    {
+        let data = serde_json::to_string(value)?;
+        match ttl {
+            Some(ttl) => {
+                self.multiplexed_connection
+                    .set_ex::<&str, String, ()>(key, data, ttl)
+                    .await?
+            }
+            None => {
+                self.multiplexed_connection
+                    .set::<&str, String, ()>(key, data)
+                    .await?
+            }
+        }
+        Ok(())
+    }
+
     /// Publishes the data to the redis stream.
     /// Returns a `bool` that is true if the data was published and false otherwise.
     pub async fn publish<ST>(&mut self, stream_name: &str, data: &ST) -> bool
@@ -226,6 +266,51 @@ mod tests {
         cleanup(&mut service).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_redis_service_get_value_missing() -> Result<(), anyhow::Error> {
+        // Setup
+        let (redis_connection_string, _container) = get_redis_url().await;
+        let random_key = Alphanumeric.sample_string(&mut rand::thread_rng(), 6);
+
+        let mut service = RedisService::new(&redis_connection_string).await;
+
+        // Test
+        let result = service.get_value::<NewsPost>(&random_key).await?;
+
+        // Assert
+        assert_eq!(result, None);
+        cleanup(&mut service).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_redis_service_set_and_get_value() -> Result<(), anyhow::Error> {
+        // Setup
+        let (redis_connection_string, _container) = get_redis_url().await;
+        let random_key = Alphanumeric.sample_string(&mut rand::thread_rng(), 6);
+
+        let mut service = RedisService::new(&redis_connection_string).await;
+        let post = NewsPost {
+            image: Some(String::from("i")),
+            title: Some(String::from("t")),
+            summary: Some(String::from("s")),
+            link: Some(String::from("l")),
+            author: Some(String::from("a")),
+            ..Default::default()
+        };
+
+        // Test
+        service.set_value(&random_key, &post, Some(10)).await?;
+        let result = service.get_value::<NewsPost>(&random_key).await?;
+
+        // Assert
+        assert_eq!(result, Some(post));
+        cleanup(&mut service).await;
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_redis_service_publish() {
@@ -242,6 +327,7 @@ mod tests {
             summary: Some(String::from("s")),
             link: Some(String::from("l")),
             author: Some(String::from("a")),
+            ..Default::default()
         };
         let result = service.publish(&random_stream_name, &post).await;
 
@@ -270,6 +356,7 @@ mod tests {
             summary: Some(String::from("s")),
             link: Some(String::from("l")),
             author: Some(String::from("a")),
+            ..Default::default()
         };
         let _ = service.publish(&random_stream_name, &post).await;
 
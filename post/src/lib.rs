@@ -1,8 +1,13 @@
 use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 
+mod normalize;
+
+pub use normalize::{normalize_text, truncate_text};
+
 /// NewsPost represents a news post.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialOrd, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialOrd, PartialEq)]
 pub struct NewsPost {
     /// A URL containing the image of the post.
     pub image: Option<String>,
@@ -14,6 +19,30 @@ pub struct NewsPost {
     pub link: Option<String>,
     /// The author of the post.
     pub author: Option<String>,
+    /// When the post was published, if known.
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    /// The ISO 639-1 code of the post's language, e.g. `ro`.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Describes the image, set when the scraper validated it.
+    #[serde(default)]
+    pub image_info: Option<ImageInfo>,
+    /// The plain text of the article's body, paragraphs are separated by blank lines.
+    #[serde(default)]
+    pub body: Option<String>,
+    /// The hashtags of the post, without the `#`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// ImageInfo describes a post's image.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialOrd, PartialEq)]
+pub struct ImageInfo {
+    /// The MIME type of the image, e.g. `image/jpeg`.
+    pub mime_type: String,
+    /// The size of the image in bytes.
+    pub size: u64,
 }
 
 impl NewsPost {
@@ -21,6 +50,20 @@ impl NewsPost {
     pub fn is_complete(&self) -> bool {
         self.title.is_some() && self.link.is_some()
     }
+
+    /// Returns true if the post was published more than `max_age` before `now`.
+    /// Posts without a publication time are never stale.
+    pub fn is_stale(&self, max_age: TimeDelta, now: DateTime<Utc>) -> bool {
+        self.published_at
+            .is_some_and(|published_at| now - published_at > max_age)
+    }
+
+    /// Normalizes the title, summary and author with [normalize_text], empty ones become `None`.
+    pub fn normalize(&mut self) {
+        self.title = normalize::normalize_optional_text(self.title.take());
+        self.summary = normalize::normalize_optional_text(self.summary.take());
+        self.author = normalize::normalize_optional_text(self.author.take());
+    }
 }
 
 /// Publisher trait defines the contract for publishing news posts.
@@ -29,11 +72,54 @@ pub trait Publisher {
     /// publish_post publishes the NewsPost.
     /// Returns an error if the publishing fails.
     async fn publish_post(&mut self, post: NewsPost) -> Result<(), anyhow::Error>;
+
+    /// Returns the maximum number of characters of the post's summary once published.
+    fn max_summary_chars(&self, post: &NewsPost) -> usize;
 }
 
-/// Extracts the tweet's text from a newspost.
+/// Returns the number of characters left for the summary by [extract_text_from_post].
+pub fn summary_budget(post: &NewsPost, character_budget: usize) -> usize {
+    let title = post
+        .title
+        .as_deref()
+        .unwrap_or("Post Title")
+        .chars()
+        .count();
+    let link = post.link.as_deref().unwrap_or_default().chars().count();
+    let budget = character_budget.saturating_sub(link + 1 + title + 2);
+    let hashtags = format_hashtags(&post.tags, budget.saturating_sub(1))
+        .chars()
+        .count();
+    if hashtags == 0 {
+        budget
+    } else {
+        budget.saturating_sub(hashtags + 1)
+    }
+}
+
+/// Formats the tags as space separated hashtags, e.g. `#politica #economie`.
+/// Only the first tags fitting in `max_chars` characters are kept.
+pub fn format_hashtags(tags: &[String], max_chars: usize) -> String {
+    let mut hashtags = String::new();
+    for tag in tags.iter().filter(|tag| !tag.is_empty()) {
+        let hashtag = format!("#{tag}");
+        let separator = usize::from(!hashtags.is_empty());
+        if hashtags.chars().count() + separator + hashtag.chars().count() > max_chars {
+            break;
+        }
+        if separator == 1 {
+            hashtags.push(' ');
+        }
+        hashtags.push_str(&hashtag);
+    }
+    hashtags
+}
+
+/// Extracts the tweet's text from a newspost, the budget is counted in characters.
+/// The title and summary are cut at a word boundary with [truncate_text].
 pub fn extract_text_from_post(value: NewsPost, character_budget: i32) -> String {
     let mut status = String::new();
+    let count = |text: &str| text.chars().count() as i32;
 
     // The character budget for mastodon.social.
     let mut character_budget: i32 = character_budget;
@@ -42,26 +128,31 @@ pub fn extract_text_from_post(value: NewsPost, character_budget: i32) -> String
     let link = value.link.unwrap_or(String::from(""));
 
     // reserve space for the link + one space
-    character_budget -= link.len() as i32 + 1;
+    character_budget -= count(&link) + 1;
+
+    // reserve space for the hashtags which fit after the title + one space
+    let hashtags_budget = character_budget - count(&title) - 2 - 1;
+    let hashtags = format_hashtags(&value.tags, hashtags_budget.max(0) as usize);
+    if !hashtags.is_empty() {
+        character_budget -= count(&hashtags) + 1;
+    }
 
     // Push the title
     if character_budget > 0 {
-        status.push_str(
-            title
-                .get(0..character_budget as usize)
-                .unwrap_or(title.as_str()),
-        );
-        character_budget -= title.len() as i32 + 2;
+        status.push_str(&truncate_text(&title, character_budget as usize));
+        character_budget -= count(&title) + 2;
         status.push('\n')
     }
 
     // Push the summary
     if character_budget > 0 {
-        status.push_str(
-            summary
-                .get(0..character_budget as usize)
-                .unwrap_or(summary.as_str()),
-        );
+        status.push_str(&truncate_text(&summary, character_budget as usize));
+        status.push('\n')
+    }
+
+    // Push the hashtags
+    if !hashtags.is_empty() {
+        status.push_str(&hashtags);
         status.push('\n')
     }
 
@@ -69,3 +160,106 @@ pub fn extract_text_from_post(value: NewsPost, character_budget: i32) -> String
     status.push_str(link.as_str());
     status
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_news_post_is_stale() {
+        let now = Utc::now();
+        let mut post = NewsPost {
+            title: Some(String::from("Title")),
+            link: Some(String::from("https://some-news.ro/title")),
+            ..Default::default()
+        };
+
+        assert!(!post.is_stale(TimeDelta::hours(24), now));
+
+        post.published_at = Some(now - TimeDelta::hours(2));
+        assert!(!post.is_stale(TimeDelta::hours(24), now));
+
+        post.published_at = Some(now - TimeDelta::days(3));
+        assert!(post.is_stale(TimeDelta::hours(24), now));
+    }
+
+    #[test]
+    fn test_summary_budget() {
+        let post = NewsPost {
+            title: Some(String::from("Știri")),
+            link: Some(String::from("https://some-news.ro/stiri")),
+            ..Default::default()
+        };
+
+        assert_eq!(summary_budget(&post, 280), 280 - 26 - 1 - 5 - 2);
+        assert_eq!(summary_budget(&post, 10), 0);
+    }
+
+    #[test]
+    fn test_format_hashtags() {
+        let tags = vec![
+            String::from("politica"),
+            String::from("economie"),
+            String::from("bucuresti"),
+        ];
+
+        assert_eq!(
+            format_hashtags(&tags, 100),
+            "#politica #economie #bucuresti"
+        );
+        assert_eq!(format_hashtags(&tags, 19), "#politica #economie");
+        assert_eq!(format_hashtags(&tags, 5), "");
+        assert_eq!(format_hashtags(&[], 100), "");
+    }
+
+    #[test]
+    fn test_extract_text_from_post_counts_characters() {
+        let post = NewsPost {
+            title: Some(String::from("Știință și țară")),
+            summary: Some(String::from("Școlile își încep cursurile în țară")),
+            link: Some(String::from("https://some-news.ro/stiinta")),
+            ..Default::default()
+        };
+        let budget = 28 + 1 + 15 + 2 + 35;
+
+        // A summary sized to the budget is kept whole.
+        assert_eq!(summary_budget(&post, budget), 35);
+        assert_eq!(
+            extract_text_from_post(post.clone(), budget as i32),
+            "Știință și țară\nȘcolile își încep cursurile în țară\nhttps://some-news.ro/stiinta"
+        );
+        // A longer one is cut at a word boundary.
+        assert_eq!(
+            extract_text_from_post(post, budget as i32 - 5),
+            "Știință și țară\nȘcolile își încep cursurile…\nhttps://some-news.ro/stiinta"
+        );
+    }
+
+    #[test]
+    fn test_extract_text_from_post_with_hashtags() {
+        let mut post = NewsPost {
+            title: Some(String::from("Title")),
+            summary: Some(String::from("A summary which is long enough to be cut.")),
+            link: Some(String::from("https://some-news.ro/title")),
+            tags: vec![String::from("politica"), String::from("economie")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            extract_text_from_post(post.clone(), 500),
+            "Title\nA summary which is long enough to be cut.\n#politica #economie\nhttps://some-news.ro/title"
+        );
+        assert_eq!(summary_budget(&post, 500), 500 - 26 - 1 - 5 - 2 - 19 - 1);
+        // The hashtags which don't fit are dropped, the link is never cut.
+        assert_eq!(
+            extract_text_from_post(post.clone(), 50),
+            "Title\nA…\n#politica\nhttps://some-news.ro/title"
+        );
+
+        post.tags.clear();
+        assert_eq!(
+            extract_text_from_post(post, 500),
+            "Title\nA summary which is long enough to be cut.\nhttps://some-news.ro/title"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use unicode_normalization::UnicodeNormalization;
+
+/// Tags which are dropped without separating the surrounding text.
+const INLINE_TAGS: [&str; 15] = [
+    "a", "abbr", "b", "bdi", "cite", "code", "em", "i", "mark", "q", "s", "small", "span",
+    "strong", "u",
+];
+
+/// Returns true if the text starting after a `<` looks like a tag, a comment or a doctype.
+fn starts_tag(rest: &str) -> bool {
+    rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!')
+}
+
+/// Returns true if the tag, given without its angle brackets, is an inline one.
+fn is_inline_tag(tag: &str) -> bool {
+    let name = tag
+        .trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    INLINE_TAGS.contains(&name.as_str())
+}
+
+/// Removes the markup, block tags such as `<br>` or `<p>` are replaced by a space.
+fn strip_tags(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('>') {
+            Some(end) if starts_tag(after) => {
+                if !is_inline_tag(&after[..end]) {
+                    result.push(' ');
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                result.push('<');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Replaces the typographic quotes with their ASCII counterparts.
+fn normalize_quote(c: char) -> char {
+    match c {
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{00AB}' | '\u{00BB}'
+        | '\u{2033}' => '"',
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2039}' | '\u{203A}'
+        | '\u{2032}' => '\'',
+        c => c,
+    }
+}
+
+/// Normalizes scraped text: strips the markup, decodes the HTML entities, normalizes the quotes
+/// and the Unicode composition and collapses the whitespace.
+pub fn normalize_text(text: &str) -> String {
+    let text = strip_tags(text);
+    let text = html_escape::decode_html_entities(&text);
+    text.nfc()
+        .map(normalize_quote)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cuts the text to at most `max_chars` characters at a word boundary, marking the cut with an ellipsis.
+pub fn truncate_text(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let end = text
+        .char_indices()
+        .nth(max_chars.saturating_sub(1))
+        .map_or(text.len(), |(i, _)| i);
+    let cut = &text[..end];
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(space) if space > 0 && !text[end..].starts_with(char::is_whitespace) => &cut[..space],
+        _ => cut,
+    };
+    format!("{}…", cut.trim_end())
+}
+
+/// Normalizes the optional text, empty results become `None`.
+pub(crate) fn normalize_optional_text(text: Option<String>) -> Option<String> {
+    text.map(|t| normalize_text(&t)).filter(|t| !t.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_text_decodes_entities() {
+        assert_eq!(
+            normalize_text("Tom &amp; Jerry &#8222;live&#8221; &hellip;"),
+            "Tom & Jerry \"live\" …"
+        );
+        assert_eq!(normalize_text("&amp;lt;b&amp;gt;"), "&lt;b&gt;");
+    }
+
+    #[test]
+    fn test_normalize_text_strips_tags() {
+        assert_eq!(
+            normalize_text("<span class=\"label\">LIVE</span> The <em>first</em> article"),
+            "LIVE The first article"
+        );
+        assert_eq!(
+            normalize_text("<p>First line<br/>Second line</p><!-- comment -->"),
+            "First line Second line"
+        );
+        assert_eq!(normalize_text("1 < 2 &lt;b&gt;"), "1 < 2 <b>");
+    }
+
+    #[test]
+    fn test_normalize_text_collapses_whitespace() {
+        assert_eq!(
+            normalize_text("\n  The\ttitle&nbsp;of the\u{00A0}post  \n"),
+            "The title of the post"
+        );
+    }
+
+    #[test]
+    fn test_normalize_text_quotes_and_composition() {
+        assert_eq!(
+            normalize_text("„Citat” «cu» ‘ghilimele’ l’été"),
+            "\"Citat\" \"cu\" 'ghilimele' l'été"
+        );
+        assert_eq!(normalize_text("s\u{0326}i t\u{0326}ara"), "și țara");
+    }
+
+    #[test]
+    fn test_normalize_optional_text() {
+        assert_eq!(
+            normalize_optional_text(Some(String::from(" <p></p> "))),
+            None
+        );
+        assert_eq!(normalize_optional_text(None), None);
+        assert_eq!(
+            normalize_optional_text(Some(String::from("<b>Text</b>"))),
+            Some(String::from("Text"))
+        );
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("Un text scurt", 20), "Un text scurt");
+        assert_eq!(
+            truncate_text("Un text mai lung decât limita", 16),
+            "Un text mai…"
+        );
+        assert_eq!(
+            truncate_text("Un text mai lung decât limita", 12),
+            "Un text mai…"
+        );
+        assert_eq!(truncate_text("Cuvântfărăspații", 8), "Cuvântf…");
+    }
+}
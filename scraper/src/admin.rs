@@ -0,0 +1,273 @@
+use crate::status::{RunPost, SourceStatus, SourceStatuses};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use log::info;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, watch};
+
+/// Asks the scheduler to scrape sources right away.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrapeRequest {
+    /// Scrape all the enabled sources.
+    All,
+    /// Scrape the source with the given name.
+    Source(String),
+}
+
+/// The state shared by the admin API handlers.
+#[derive(Clone)]
+pub struct AdminState {
+    pub statuses: Arc<SourceStatuses>,
+    pub scrape_tx: mpsc::Sender<ScrapeRequest>,
+}
+
+type AdminResult<T> = Result<T, (StatusCode, String)>;
+
+fn not_found(name: &str) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, format!("unknown source: {name}"))
+}
+
+/// Builds the admin API router.
+pub fn router(state: AdminState) -> Router {
+    Router::new()
+        .route("/sources", get(list_sources))
+        .route("/sources/{name}", get(get_source))
+        .route("/sources/{name}/posts", get(get_source_posts))
+        .route("/sources/{name}/scrape", post(scrape_source))
+        .route("/sources/{name}/enable", post(enable_source))
+        .route("/sources/{name}/disable", post(disable_source))
+        .route("/scrape", post(scrape_all))
+        .with_state(state)
+}
+
+/// Serves the admin API until shutdown is requested.
+pub async fn serve(
+    listener: TcpListener,
+    state: AdminState,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> Result<(), anyhow::Error> {
+    info!("Admin API listening on {}", listener.local_addr()?);
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async move {
+            let _ = shutdown_rx.wait_for(|shutdown| *shutdown).await;
+        })
+        .await?;
+    Ok(())
+}
+
+async fn list_sources(State(state): State<AdminState>) -> Json<Vec<SourceStatus>> {
+    Json(state.statuses.list())
+}
+
+async fn get_source(
+    State(state): State<AdminState>,
+    Path(name): Path<String>,
+) -> AdminResult<Json<SourceStatus>> {
+    state
+        .statuses
+        .get(&name)
+        .map(Json)
+        .ok_or_else(|| not_found(&name))
+}
+
+async fn get_source_posts(
+    State(state): State<AdminState>,
+    Path(name): Path<String>,
+) -> AdminResult<Json<Vec<RunPost>>> {
+    state
+        .statuses
+        .get(&name)
+        .map(|status| Json(status.posts))
+        .ok_or_else(|| not_found(&name))
+}
+
+/// Sends the scrape request to the scheduler.
+async fn request_scrape(state: &AdminState, request: ScrapeRequest) -> AdminResult<StatusCode> {
+    state.scrape_tx.send(request).await.map_err(|_| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            String::from("the scheduler is not running"),
+        )
+    })?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn scrape_source(
+    State(state): State<AdminState>,
+    Path(name): Path<String>,
+) -> AdminResult<StatusCode> {
+    let status = state.statuses.get(&name).ok_or_else(|| not_found(&name))?;
+    if !status.enabled {
+        return Err((StatusCode::CONFLICT, format!("source {name} is disabled")));
+    }
+    // The scheduler skips the sources whose previous run hasn't finished.
+    if status.running {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("source {name} is already running"),
+        ));
+    }
+    request_scrape(&state, ScrapeRequest::Source(name)).await
+}
+
+async fn scrape_all(State(state): State<AdminState>) -> AdminResult<StatusCode> {
+    request_scrape(&state, ScrapeRequest::All).await
+}
+
+async fn enable_source(
+    State(state): State<AdminState>,
+    Path(name): Path<String>,
+) -> AdminResult<Json<SourceStatus>> {
+    info!("Enabling source {name}");
+    state
+        .statuses
+        .set_enabled(&name, true)
+        .map(Json)
+        .ok_or_else(|| not_found(&name))
+}
+
+async fn disable_source(
+    State(state): State<AdminState>,
+    Path(name): Path<String>,
+) -> AdminResult<Json<SourceStatus>> {
+    info!("Disabling source {name}");
+    state
+        .statuses
+        .set_enabled(&name, false)
+        .map(Json)
+        .ok_or_else(|| not_found(&name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::RunStatus;
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use post::NewsPost;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    fn state() -> (AdminState, mpsc::Receiver<ScrapeRequest>) {
+        let (scrape_tx, scrape_rx) = mpsc::channel(8);
+        let state = AdminState {
+            statuses: Arc::new(SourceStatuses::new(["g4media", "hotnews"])),
+            scrape_tx,
+        };
+        (state, scrape_rx)
+    }
+
+    async fn call(state: &AdminState, method: &str, uri: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_sources() {
+        let (state, _scrape_rx) = state();
+        state.statuses.finish_run(
+            "hotnews",
+            RunStatus::Failed(String::from("timed out after 300 seconds")),
+            None,
+            None,
+        );
+
+        let (status, sources) = call(&state, "GET", "/sources").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(sources[0]["name"], "g4media");
+        assert_eq!(sources[0]["last_status"], Value::Null);
+        assert_eq!(
+            sources[1]["last_status"],
+            json!({"status": "failed", "reason": "timed out after 300 seconds"})
+        );
+
+        let (status, source) = call(&state, "GET", "/sources/g4media").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(source["enabled"], true);
+
+        let (status, _) = call(&state, "GET", "/sources/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_source_posts() {
+        let (state, _scrape_rx) = state();
+        let post = NewsPost {
+            title: Some(String::from("Horoscop")),
+            link: Some(String::from("https://some-news.ro/horoscop")),
+            ..Default::default()
+        };
+        state.statuses.finish_run(
+            "g4media",
+            RunStatus::Succeeded,
+            Some(vec![RunPost {
+                post,
+                dropped: Some(String::from("excluded by rule horoscope")),
+            }]),
+            None,
+        );
+
+        let (status, posts) = call(&state, "GET", "/sources/g4media/posts").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(posts[0]["title"], "Horoscop");
+        assert_eq!(posts[0]["dropped"], "excluded by rule horoscope");
+    }
+
+    #[tokio::test]
+    async fn test_scrape_requests() {
+        let (state, mut scrape_rx) = state();
+
+        let (status, _) = call(&state, "POST", "/sources/hotnews/scrape").await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(
+            scrape_rx.recv().await,
+            Some(ScrapeRequest::Source(String::from("hotnews")))
+        );
+
+        let (status, _) = call(&state, "POST", "/scrape").await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(scrape_rx.recv().await, Some(ScrapeRequest::All));
+
+        let (status, _) = call(&state, "POST", "/sources/missing/scrape").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_enable_source() {
+        let (state, _scrape_rx) = state();
+
+        let (status, source) = call(&state, "POST", "/sources/hotnews/disable").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(source["enabled"], false);
+        assert!(!state.statuses.is_enabled("hotnews"));
+
+        let (status, _) = call(&state, "POST", "/sources/hotnews/scrape").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, source) = call(&state, "POST", "/sources/hotnews/enable").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(source["enabled"], true);
+    }
+
+    #[tokio::test]
+    async fn test_scrape_running_source() {
+        let (state, mut scrape_rx) = state();
+        state.statuses.start_run("g4media");
+
+        let (status, _) = call(&state, "POST", "/sources/g4media/scrape").await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(scrape_rx.try_recv().is_err());
+    }
+}
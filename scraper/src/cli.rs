@@ -1,21 +1,172 @@
 use clap::Parser;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+/// A feed source given on the command line as `AUTHOR=URL`.
+#[derive(Clone, Debug)]
+pub struct FeedSource {
+    pub default_author: String,
+    pub url: String,
+}
+
+/// Parses a feed source from the `AUTHOR=URL` format.
+fn parse_feed_source(value: &str) -> Result<FeedSource, String> {
+    let (author, url) = value
+        .split_once('=')
+        .ok_or_else(|| format!("invalid feed `{value}`, expected AUTHOR=URL"))?;
+    if author.trim().is_empty() || url.trim().is_empty() {
+        return Err(format!("invalid feed `{value}`, expected AUTHOR=URL"));
+    }
+
+    Ok(FeedSource {
+        default_author: author.trim().to_string(),
+        url: url.trim().to_string(),
+    })
+}
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct CliArgs {
     /// Redis host
-    #[arg(short, long)]
-    pub redis_connection_string: String,
+    #[arg(short, long, required_unless_present = "dry_run")]
+    pub redis_connection_string: Option<String>,
 
     /// Redis stream name
-    #[arg(short = 't', long)]
-    pub redis_stream_name: String,
+    #[arg(short = 't', long, required_unless_present = "dry_run")]
+    pub redis_stream_name: Option<String>,
 
-    /// The scraping interval in minutes
+    /// Scrape the sources once and print their posts as JSON lines, without Redis.
+    /// Exits with an error if any source failed.
+    #[arg(short = 'n', long, default_value_t = false)]
+    pub dry_run: bool,
+
+    /// Serve the admin HTTP API on the given address, e.g. 127.0.0.1:8080.
+    #[arg(long)]
+    pub admin_address: Option<SocketAddr>,
+
+    /// Only scrape the source with the given name. Can be repeated.
+    #[arg(long = "source")]
+    pub only_sources: Vec<String>,
+
+    /// The scraping interval in minutes for the sources without their own schedule
     #[arg(short, long, default_value_t = 60)]
     pub scrape_interval_minutes: u32,
 
     /// Limit the number of posts to the given amount. Applies individually to each source.
     #[arg(short = 'm', long, default_value_t = 100)]
     pub max_posts_per_run: u64,
+
+    /// A TOML file defining the sources to scrape. Defaults to the built-in sources.
+    #[arg(short = 'c', long)]
+    pub config_file: Option<PathBuf>,
+
+    /// Persist the ETag and Last-Modified of each source in Redis, in addition to memory.
+    #[arg(long, default_value_t = false)]
+    pub persist_http_cache: bool,
+
+    /// Don't fetch the robots.txt of the scraped hosts and don't honour it.
+    #[arg(long, default_value_t = false)]
+    pub ignore_robots_txt: bool,
+
+    /// The minimum delay in milliseconds between two requests to the same host.
+    /// The host's robots.txt Crawl-delay is used when it's longer.
+    #[arg(long, default_value_t = 1000)]
+    pub min_host_delay_millis: u64,
+
+    /// The time in seconds after which connecting to a host is abandoned.
+    #[arg(long, default_value_t = 10)]
+    pub connect_timeout_seconds: u64,
+
+    /// The time in seconds after which a response which stopped sending data is abandoned.
+    #[arg(long, default_value_t = 30)]
+    pub read_timeout_seconds: u64,
+
+    /// The time in seconds after which the scrape of a source is abandoned, including its enrichment.
+    /// Applies to the sources without their own `timeout_seconds`.
+    #[arg(long, default_value_t = 300)]
+    pub source_timeout_seconds: u64,
+
+    /// The maximum number of sources scraped at once.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_concurrent_sources: u32,
+
+    /// Fetch each post's article and fill the missing fields from its OpenGraph and JSON-LD metadata.
+    #[arg(short = 'e', long, default_value_t = false)]
+    pub enrich_posts: bool,
+
+    /// The maximum number of articles fetched at once by the enrichment, per source.
+    #[arg(long, default_value_t = 4)]
+    pub enrichment_concurrency: usize,
+
+    /// The time in seconds after which an article fetch is abandoned by the enrichment.
+    #[arg(long, default_value_t = 10)]
+    pub enrichment_timeout_seconds: u64,
+
+    /// Fetch each post's article and extract its text into the post's body, dropping the navigation,
+    /// ads and comments. Can be used with or without --enrich-posts.
+    #[arg(long, default_value_t = false)]
+    pub extract_body: bool,
+
+    /// The maximum number of characters of the extracted body, longer texts are cut.
+    #[arg(long, default_value_t = 5000)]
+    pub body_max_chars: usize,
+
+    /// Fetch the start of each sent post's image and check its status, type, size and dimensions.
+    /// Invalid images are replaced by the fallback image, or removed.
+    #[arg(long, default_value_t = false)]
+    pub validate_images: bool,
+
+    /// The maximum size in bytes of a valid image, the default is Bluesky's limit.
+    #[arg(long, default_value_t = 1_000_000)]
+    pub image_max_bytes: u64,
+
+    /// The minimum width in pixels of a valid image.
+    #[arg(long, default_value_t = 200)]
+    pub image_min_width: usize,
+
+    /// The minimum height in pixels of a valid image.
+    #[arg(long, default_value_t = 100)]
+    pub image_min_height: usize,
+
+    /// The URL of the image replacing the invalid ones.
+    #[arg(long)]
+    pub fallback_image_url: Option<String>,
+
+    /// The maximum number of images fetched at once by the validation, per source.
+    #[arg(long, default_value_t = 4)]
+    pub image_validation_concurrency: usize,
+
+    /// The time in seconds after which an image fetch is abandoned by the validation.
+    #[arg(long, default_value_t = 10)]
+    pub image_validation_timeout_seconds: u64,
+
+    /// The number of recent healthy runs a source's statistics are compared against.
+    #[arg(long, default_value_t = 10)]
+    pub health_window_runs: usize,
+
+    /// The fraction by which a source's counts must drop against its recent runs to flag it as degraded.
+    #[arg(long, default_value_t = 0.5)]
+    pub health_drop_threshold: f64,
+
+    /// Drop the posts published more than this number of hours ago. Posts without a publication time are kept.
+    #[arg(long)]
+    pub max_post_age_hours: Option<u32>,
+
+    /// The minimum confidence, between 0 and 1, of the detected language of a post.
+    /// Posts detected with a lower confidence are published with the bot's default language.
+    #[arg(long, default_value_t = 0.15)]
+    pub language_min_confidence: f64,
+
+    /// The number of hours during which a post similar to a published one, from any source, is not published.
+    #[arg(long, default_value_t = 48)]
+    pub duplicate_window_hours: u32,
+
+    /// The maximum number of differing bits between the SimHash of two titles to consider them duplicates.
+    /// Set to 0 to only match almost identical titles.
+    #[arg(long, default_value_t = 8)]
+    pub duplicate_title_distance: u32,
+
+    /// An RSS or Atom feed to scrape, given as AUTHOR=URL. Can be repeated.
+    #[arg(short = 'f', long = "feed", value_parser = parse_feed_source)]
+    pub feeds: Vec<FeedSource>,
 }
@@ -0,0 +1,471 @@
+use crate::filters::{FilterRuleConfig, PostFilter};
+use crate::hashtags::{HashtagGenerator, HashtagsConfig};
+use crate::schedule::{Schedule, ScheduleConfig};
+use crate::scraper::ScrapableWebPage;
+use crate::targets::{Feed, PaginatedWebPage, SelectorWebPage, SelectorsConfig, WordPress};
+use anyhow::{Context, anyhow};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The scraper configuration file.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScraperConfig {
+    /// The sources to scrape.
+    #[serde(default)]
+    pub sources: Vec<SourceConfig>,
+    /// The filter rules applied to the posts of all the sources.
+    #[serde(default)]
+    pub filters: Vec<FilterRuleConfig>,
+    /// How the hashtags of the posts are derived.
+    #[serde(default)]
+    pub hashtags: HashtagsConfig,
+}
+
+/// A single source entry from the configuration file.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SourceConfig {
+    /// The unique name of the source.
+    pub name: String,
+    /// The URL of the page or feed.
+    pub url: String,
+    /// The author used when a post has none.
+    pub default_author: String,
+    /// When the source is scraped, defaults to the scrape interval given on the command line.
+    #[serde(default)]
+    pub schedule: Option<ScheduleConfig>,
+    /// The filter rules applied to the posts of this source, after the global ones.
+    #[serde(default)]
+    pub filters: Vec<FilterRuleConfig>,
+    /// Additional pages scraped like the `url`, e.g. category pages.
+    #[serde(default)]
+    pub extra_urls: Vec<String>,
+    /// How many next pages are followed from each page, for the targets supporting pagination.
+    #[serde(default)]
+    pub max_depth: usize,
+    /// The time in seconds after which a scrape of the source is abandoned, defaults to `--source-timeout-seconds`.
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+    /// The hashtags added to the source's posts after the ones matched by keywords.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The kind of source.
+    #[serde(flatten)]
+    pub kind: SourceKind,
+}
+
+/// The kind of source and its specific settings.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SourceKind {
+    /// A web page scraped with CSS selectors.
+    Selectors { selectors: Box<SelectorsConfig> },
+    /// An RSS 2.0 or Atom feed.
+    Feed,
+    /// A WordPress site read through its REST API.
+    #[serde(rename = "wordpress")]
+    WordPress,
+}
+
+/// A named source ready to be scraped.
+#[derive(Clone)]
+pub struct Source {
+    pub name: String,
+    pub page: Arc<dyn ScrapableWebPage>,
+    /// The source's own schedule, `None` uses the default schedule.
+    pub schedule: Option<Schedule>,
+    /// Decides which of the source's posts are kept.
+    pub filter: PostFilter,
+    /// The source's own deadline, `None` uses the default deadline.
+    pub timeout: Option<Duration>,
+    /// Derives the hashtags of the source's posts.
+    pub hashtags: HashtagGenerator,
+}
+
+impl Source {
+    /// Creates a new source from the given web page.
+    pub fn new<P>(name: &str, page: P) -> Self
+    where
+        P: ScrapableWebPage + 'static,
+    {
+        Source {
+            name: name.to_string(),
+            page: Arc::new(page),
+            schedule: None,
+            filter: PostFilter::default(),
+            timeout: None,
+            hashtags: HashtagGenerator::default(),
+        }
+    }
+}
+
+impl SourceConfig {
+    /// Builds the source described by this entry, the global filter rules are applied before its own.
+    pub fn build(
+        &self,
+        global_filters: &[FilterRuleConfig],
+        hashtags: &HashtagsConfig,
+    ) -> Result<Source, anyhow::Error> {
+        let schedule = self
+            .schedule
+            .as_ref()
+            .map(Schedule::try_from)
+            .transpose()
+            .with_context(|| format!("invalid schedule for source {}", self.name))?;
+        let rules: Vec<FilterRuleConfig> = global_filters
+            .iter()
+            .chain(&self.filters)
+            .cloned()
+            .collect();
+        let filter = PostFilter::new(&rules)
+            .with_context(|| format!("invalid filters for source {}", self.name))?;
+        let page: Arc<dyn ScrapableWebPage> = match &self.kind {
+            SourceKind::Selectors { selectors } => Arc::new(
+                SelectorWebPage::new(&self.url, &self.default_author, selectors)
+                    .with_context(|| format!("invalid selectors for source {}", self.name))?,
+            ),
+            SourceKind::Feed => Arc::new(Feed::new(&self.url, &self.default_author)),
+            SourceKind::WordPress => Arc::new(WordPress::new(&self.url, &self.default_author)),
+        };
+        let page: Arc<dyn ScrapableWebPage> = if self.extra_urls.is_empty() && self.max_depth == 0 {
+            page
+        } else {
+            Arc::new(PaginatedWebPage::new(
+                page,
+                &self.extra_urls,
+                self.max_depth,
+            ))
+        };
+        Ok(Source {
+            name: self.name.clone(),
+            page,
+            schedule,
+            filter,
+            timeout: self.timeout_seconds.map(Duration::from_secs),
+            hashtags: HashtagGenerator::new(hashtags, &self.tags),
+        })
+    }
+}
+
+impl ScraperConfig {
+    /// Parses the configuration from a TOML string.
+    pub fn parse(data: &str) -> Result<Self, anyhow::Error> {
+        let config: ScraperConfig = toml::from_str(data)?;
+
+        let mut names = HashSet::new();
+        for source in &config.sources {
+            if !names.insert(source.name.as_str()) {
+                return Err(anyhow!("duplicate source name: {}", source.name));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Reads the configuration from a TOML file.
+    pub fn from_file(path: &Path) -> Result<Self, anyhow::Error> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        ScraperConfig::parse(&data)
+            .with_context(|| format!("failed to parse config file {}", path.display()))
+    }
+
+    /// Builds all the sources defined in the configuration.
+    pub fn build_sources(&self) -> Result<Vec<Source>, anyhow::Error> {
+        self.sources
+            .iter()
+            .map(|s| s.build(&self.filters, &self.hashtags))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::{FilterAction, PostField};
+    use post::NewsPost;
+    use std::collections::BTreeMap;
+
+    const CONFIG: &str = r#"
+[[filters]]
+name = "horoscope"
+action = "exclude"
+fields = ["title"]
+keywords = ["horoscop"]
+
+[hashtags]
+max_tags = 2
+keywords = { guvernul = "Guvern", "inflația" = "Economie" }
+
+[[sources]]
+name = "g4media"
+type = "selectors"
+url = "https://www.g4media.ro/"
+default_author = "G4Media"
+extra_urls = ["https://www.g4media.ro/politica"]
+max_depth = 2
+timeout_seconds = 60
+
+[sources.selectors]
+container = "div.article"
+title = "h2.article__title > a"
+image = "figure picture img, .article__media img"
+author = ".article__eyebrow a[rel='author']"
+excerpt = ".article__excerpt"
+
+[[sources]]
+name = "some-feed"
+type = "feed"
+url = "https://some-news.ro/feed"
+default_author = "Some News"
+schedule = { interval_minutes = 5 }
+tags = ["Știri"]
+
+[[sources.filters]]
+name = "sports"
+action = "exclude"
+fields = ["link"]
+regex = "^/sport/"
+
+[[sources]]
+name = "hotnews"
+type = "wordpress"
+url = "https://hotnews.ro"
+default_author = "HotNews"
+
+[sources.schedule]
+cron = "*/10 6-23 * * *"
+timezone = "Europe/Bucharest"
+"#;
+
+    #[test]
+    fn test_parse_config() -> Result<(), anyhow::Error> {
+        let config = ScraperConfig::parse(CONFIG)?;
+
+        assert_eq!(
+            config,
+            ScraperConfig {
+                sources: vec![
+                    SourceConfig {
+                        name: String::from("g4media"),
+                        url: String::from("https://www.g4media.ro/"),
+                        default_author: String::from("G4Media"),
+                        schedule: None,
+                        filters: vec![],
+                        extra_urls: vec![String::from("https://www.g4media.ro/politica")],
+                        max_depth: 2,
+                        timeout_seconds: Some(60),
+                        tags: vec![],
+                        kind: SourceKind::Selectors {
+                            selectors: Box::new(SelectorsConfig {
+                                container: String::from("div.article"),
+                                title: String::from("h2.article__title > a"),
+                                link: None,
+                                image: Some(String::from(
+                                    "figure picture img, .article__media img"
+                                )),
+                                author: Some(String::from(".article__eyebrow a[rel='author']")),
+                                excerpt: Some(String::from(".article__excerpt")),
+                                published_at: None,
+                                next_page: None,
+                                timezone: None,
+                            })
+                        },
+                    },
+                    SourceConfig {
+                        name: String::from("some-feed"),
+                        url: String::from("https://some-news.ro/feed"),
+                        default_author: String::from("Some News"),
+                        schedule: Some(ScheduleConfig::Interval {
+                            interval_minutes: 5
+                        }),
+                        filters: vec![FilterRuleConfig {
+                            name: String::from("sports"),
+                            action: FilterAction::Exclude,
+                            fields: vec![PostField::Link],
+                            keywords: vec![],
+                            regex: Some(String::from("^/sport/")),
+                        }],
+                        extra_urls: vec![],
+                        max_depth: 0,
+                        timeout_seconds: None,
+                        tags: vec![String::from("Știri")],
+                        kind: SourceKind::Feed,
+                    },
+                    SourceConfig {
+                        name: String::from("hotnews"),
+                        url: String::from("https://hotnews.ro"),
+                        default_author: String::from("HotNews"),
+                        schedule: Some(ScheduleConfig::Cron {
+                            cron: String::from("*/10 6-23 * * *"),
+                            timezone: Some(String::from("Europe/Bucharest")),
+                        }),
+                        filters: vec![],
+                        extra_urls: vec![],
+                        max_depth: 0,
+                        timeout_seconds: None,
+                        tags: vec![],
+                        kind: SourceKind::WordPress,
+                    },
+                ],
+                filters: vec![FilterRuleConfig {
+                    name: String::from("horoscope"),
+                    action: FilterAction::Exclude,
+                    fields: vec![PostField::Title],
+                    keywords: vec![String::from("horoscop")],
+                    regex: None,
+                }],
+                hashtags: HashtagsConfig {
+                    max_tags: 2,
+                    keywords: BTreeMap::from([
+                        (String::from("guvernul"), String::from("Guvern")),
+                        (String::from("inflația"), String::from("Economie")),
+                    ]),
+                },
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_sources() -> Result<(), anyhow::Error> {
+        let sources = ScraperConfig::parse(CONFIG)?.build_sources()?;
+
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources[0].name, "g4media");
+        assert!(sources[0].schedule.is_none());
+        assert!(sources[1].schedule.is_some());
+        assert_eq!(sources[0].page.get_url(), "https://www.g4media.ro/");
+        assert_eq!(
+            sources[0].page.get_urls(),
+            vec!["https://www.g4media.ro/", "https://www.g4media.ro/politica"]
+        );
+        assert_eq!(sources[0].page.get_max_depth(), 2);
+        assert_eq!(sources[1].page.get_max_depth(), 0);
+        assert_eq!(sources[0].timeout, Some(Duration::from_secs(60)));
+        assert_eq!(sources[1].timeout, None);
+        let post = NewsPost {
+            title: Some(String::from("Guvernul și inflația")),
+            link: Some(String::from("https://some-news.ro/stire")),
+            ..Default::default()
+        };
+        assert_eq!(sources[0].hashtags.tags(&post), vec!["Guvern", "Economie"]);
+        let post = NewsPost {
+            title: Some(String::from("Guvernul anunță")),
+            ..post
+        };
+        assert_eq!(sources[0].hashtags.tags(&post), vec!["Guvern"]);
+        assert_eq!(sources[1].hashtags.tags(&post), vec!["Guvern", "Stiri"]);
+        assert_eq!(sources[1].name, "some-feed");
+        assert_eq!(sources[1].page.get_url(), "https://some-news.ro/feed");
+        assert_eq!(sources[2].name, "hotnews");
+        assert_eq!(
+            sources[2].page.get_url(),
+            "https://hotnews.ro/wp-json/wp/v2/posts?_embed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_example_config_is_valid() -> Result<(), anyhow::Error> {
+        let config = ScraperConfig::parse(include_str!("../sources.toml"))?;
+
+        let sources = config.build_sources()?;
+
+        assert_eq!(sources.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_config_duplicate_names() {
+        let config = r#"
+[[sources]]
+name = "feed"
+type = "feed"
+url = "https://some-news.ro/feed"
+default_author = "Some News"
+
+[[sources]]
+name = "feed"
+type = "feed"
+url = "https://other-news.ro/feed"
+default_author = "Other News"
+"#;
+
+        assert!(ScraperConfig::parse(config).is_err());
+    }
+
+    #[test]
+    fn test_parse_config_unknown_type() {
+        let config = r#"
+[[sources]]
+name = "feed"
+type = "carrier-pigeon"
+url = "https://some-news.ro/feed"
+default_author = "Some News"
+"#;
+
+        assert!(ScraperConfig::parse(config).is_err());
+    }
+
+    #[test]
+    fn test_build_sources_invalid_schedule() -> Result<(), anyhow::Error> {
+        let config = r#"
+[[sources]]
+name = "feed"
+type = "feed"
+url = "https://some-news.ro/feed"
+default_author = "Some News"
+schedule = { cron = "every day", timezone = "Europe/Bucharest" }
+"#;
+
+        let result = ScraperConfig::parse(config)?.build_sources();
+
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_sources_invalid_filter() -> Result<(), anyhow::Error> {
+        let config = r#"
+[[filters]]
+name = "broken"
+action = "exclude"
+regex = "(unclosed"
+
+[[sources]]
+name = "feed"
+type = "feed"
+url = "https://some-news.ro/feed"
+default_author = "Some News"
+"#;
+
+        let result = ScraperConfig::parse(config)?.build_sources();
+
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_sources_invalid_selector() -> Result<(), anyhow::Error> {
+        let config = r#"
+[[sources]]
+name = "broken"
+type = "selectors"
+url = "https://some-news.ro"
+default_author = "Some News"
+
+[sources.selectors]
+container = "div..article"
+title = "h2"
+"#;
+
+        let result = ScraperConfig::parse(config)?.build_sources();
+
+        assert!(result.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use infrastructure::RedisService;
+use log::warn;
+use post::NewsPost;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use url::Url;
+
+/// Query parameters used for tracking, they don't change the page.
+const TRACKING_PARAMETERS: [&str; 12] = [
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "_ga", "ref",
+    "ref_src", "share",
+];
+
+/// Returns the URL without its fragment, tracking parameters, `www.` prefix and trailing slash,
+/// with the remaining query parameters sorted.
+pub fn canonicalize_url(url: &str) -> Option<String> {
+    let mut url = Url::parse(url).ok()?;
+    url.set_fragment(None);
+
+    if let Some(host) = url.host_str().and_then(|h| h.strip_prefix("www.")) {
+        let host = host.to_string();
+        url.set_host(Some(&host)).ok()?;
+    }
+
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| {
+            let k = k.to_ascii_lowercase();
+            !k.starts_with("utm_") && !TRACKING_PARAMETERS.contains(&k.as_str())
+        })
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    query.sort();
+    if query.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    if url.path().len() > 1 && url.path().ends_with('/') {
+        let path = url.path().trim_end_matches('/').to_string();
+        url.set_path(&path);
+    }
+    Some(url.to_string())
+}
+
+/// The 64-bit FNV-1a hash, stable across builds so the fingerprints can be stored.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The minimum share of words two titles with similar SimHashes must have in common to be duplicates,
+/// so short headlines differing in a single word, e.g. the name of a ministry, aren't confused.
+const MIN_TITLE_WORD_OVERLAP: f64 = 0.8;
+
+/// Returns the words of the title, ignoring case, diacritics and punctuation.
+fn title_words(title: &str) -> Vec<String> {
+    deunicode::deunicode(title)
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Returns the share of the distinct words of both titles found in both, from 0 to 1.
+fn word_overlap(a: &[String], b: &[String]) -> f64 {
+    let a: HashSet<&String> = a.iter().collect();
+    let b: HashSet<&String> = b.iter().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Computes the SimHash of the title's character trigrams, ignoring case, diacritics and punctuation.
+/// Similar titles have hashes which differ in few bits.
+pub fn title_simhash(title: &str) -> u64 {
+    let text = title_words(title).join(" ");
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut weights = [0i32; 64];
+    for shingle in chars.windows(3) {
+        let hash = fnv1a(shingle.iter().collect::<String>().as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| **w > 0)
+        .fold(0, |hash, (bit, _)| hash | (1 << bit))
+}
+
+/// The fingerprint of a published post.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Fingerprint {
+    /// The canonical URL of the post.
+    pub url: Option<String>,
+    /// The SimHash of the post's title.
+    pub title: Option<u64>,
+    /// The words of the post's title, confirming the titles with similar SimHashes.
+    #[serde(default)]
+    pub title_words: Vec<String>,
+    /// When the post was published.
+    pub seen_at: DateTime<Utc>,
+}
+
+impl Fingerprint {
+    /// Computes the fingerprint of the post.
+    pub fn of(post: &NewsPost, seen_at: DateTime<Utc>) -> Self {
+        Fingerprint {
+            url: post.link.as_deref().and_then(canonicalize_url),
+            title: post.title.as_deref().map(title_simhash),
+            title_words: post.title.as_deref().map(title_words).unwrap_or_default(),
+            seen_at,
+        }
+    }
+
+    /// Returns true if both fingerprints have the same URL, or titles at most `max_distance` bits apart
+    /// which share most of their words.
+    pub fn matches(&self, other: &Fingerprint, max_distance: u32) -> bool {
+        let same_url = self.url.is_some() && self.url == other.url;
+        let similar_title = match (self.title, other.title) {
+            (Some(a), Some(b)) => {
+                (a ^ b).count_ones() <= max_distance
+                    && word_overlap(&self.title_words, &other.title_words) >= MIN_TITLE_WORD_OVERLAP
+            }
+            _ => false,
+        };
+        same_url || similar_title
+    }
+}
+
+/// Detects the posts which duplicate a post published inside the time window, from any source.
+/// The fingerprints are kept in Redis when the detector is loaded from it.
+pub struct DuplicateDetector {
+    store: Option<RedisService>,
+    key: String,
+    window: TimeDelta,
+    max_distance: u32,
+    fingerprints: Vec<Fingerprint>,
+}
+
+impl DuplicateDetector {
+    /// Creates a detector which keeps the fingerprints in memory only.
+    pub fn new(window: TimeDelta, max_distance: u32) -> Self {
+        DuplicateDetector {
+            store: None,
+            key: String::new(),
+            window,
+            max_distance,
+            fingerprints: vec![],
+        }
+    }
+
+    /// Loads the fingerprints published to the stream.
+    pub async fn load(
+        mut store: RedisService,
+        stream_name: &str,
+        window: TimeDelta,
+        max_distance: u32,
+    ) -> Self {
+        let key = format!("scraper:fingerprints:{stream_name}");
+        let fingerprints = match store.get_value::<Vec<Fingerprint>>(&key).await {
+            Ok(fingerprints) => fingerprints.unwrap_or_default(),
+            Err(err) => {
+                warn!("Failed to load the post fingerprints: {err}");
+                vec![]
+            }
+        };
+        DuplicateDetector {
+            store: Some(store),
+            key,
+            window,
+            max_distance,
+            fingerprints,
+        }
+    }
+
+    /// Returns the fingerprint of the post duplicated by the given one, if any.
+    pub fn find_duplicate(&self, post: &NewsPost, now: DateTime<Utc>) -> Option<&Fingerprint> {
+        let fingerprint = Fingerprint::of(post, now);
+        self.fingerprints
+            .iter()
+            .filter(|f| now - f.seen_at <= self.window)
+            .find(|f| f.matches(&fingerprint, self.max_distance))
+    }
+
+    /// Remembers the published post, forgetting the fingerprints older than the window.
+    pub async fn remember(&mut self, post: &NewsPost, now: DateTime<Utc>) {
+        self.fingerprints.retain(|f| now - f.seen_at <= self.window);
+        self.fingerprints.push(Fingerprint::of(post, now));
+
+        let ttl = self.window.num_seconds().max(1) as u64;
+        if let Some(store) = self.store.as_mut()
+            && let Err(err) = store
+                .set_value(&self.key, &self.fingerprints, Some(ttl))
+                .await
+        {
+            warn!("Failed to save the post fingerprints: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_url() {
+        assert_eq!(
+            canonicalize_url(
+                "https://www.some-news.ro/article/?utm_source=fb&id=2&fbclid=abc&a=1#comments"
+            ),
+            Some(String::from("https://some-news.ro/article?a=1&id=2"))
+        );
+        assert_eq!(
+            canonicalize_url("https://some-news.ro/article"),
+            canonicalize_url("https://WWW.Some-News.ro/article/?utm_medium=social")
+        );
+        assert_eq!(
+            canonicalize_url("https://some-news.ro/"),
+            Some(String::from("https://some-news.ro/"))
+        );
+        assert_eq!(canonicalize_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_title_simhash_similar_titles() {
+        let distance = |a: &str, b: &str| (title_simhash(a) ^ title_simhash(b)).count_ones();
+
+        assert_eq!(
+            distance(
+                "Cod galben de ploi în jumătate de țară până duminică",
+                "Cod galben de ploi in jumatate de tara pana duminica!"
+            ),
+            0
+        );
+        assert!(
+            distance(
+                "Cod galben de ploi în jumătate de țară până duminică",
+                "ANM: Cod galben de ploi în jumătate de țară până duminică"
+            ) <= 8
+        );
+        assert!(
+            distance(
+                "Parlamentul a votat legea pensiilor în forma finală",
+                "Noul spital regional din Iași primește primii pacienți"
+            ) > 8
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_matches() {
+        let now = Utc::now();
+        let post = |title: &str, link: &str| NewsPost {
+            title: Some(title.to_string()),
+            link: Some(link.to_string()),
+            ..Default::default()
+        };
+        let original = Fingerprint::of(
+            &post(
+                "Guvernul a adoptat proiectul bugetului pe 2026",
+                "https://some-news.ro/buget",
+            ),
+            now,
+        );
+
+        let edited_title = Fingerprint::of(
+            &post(
+                "Guvernul a adoptat proiectul bugetului pe 2026.",
+                "https://other-news.ro/buget-2026",
+            ),
+            now,
+        );
+        let same_url = Fingerprint::of(
+            &post(
+                "Bugetul, adoptat",
+                "https://some-news.ro/buget?utm_source=x",
+            ),
+            now,
+        );
+        let other_story = Fingerprint::of(
+            &post(
+                "Cod galben de ploi în jumătate de țară",
+                "https://other-news.ro/ploi",
+            ),
+            now,
+        );
+
+        assert!(original.matches(&edited_title, 8));
+        assert!(original.matches(&same_url, 8));
+        assert!(!original.matches(&other_story, 8));
+    }
+
+    #[test]
+    fn test_fingerprint_short_titles_of_different_stories() {
+        let now = Utc::now();
+        let fingerprint = |title: &str, link: &str| {
+            Fingerprint::of(
+                &NewsPost {
+                    title: Some(title.to_string()),
+                    link: Some(link.to_string()),
+                    ..Default::default()
+                },
+                now,
+            )
+        };
+
+        assert!(
+            !fingerprint(
+                "Ministrul Sănătății a demisionat",
+                "https://some-news.ro/sanatate"
+            )
+            .matches(
+                &fingerprint(
+                    "Ministrul Educației a demisionat",
+                    "https://some-news.ro/educatie"
+                ),
+                8
+            )
+        );
+        assert!(
+            !fingerprint(
+                "Accident grav pe DN1, trei morți",
+                "https://some-news.ro/dn1"
+            )
+            .matches(
+                &fingerprint("Accident grav pe A1, doi răniți", "https://some-news.ro/a1"),
+                8
+            )
+        );
+        assert!(
+            fingerprint(
+                "Cod galben de ploi în jumătate de țară până duminică",
+                "https://some-news.ro/ploi"
+            )
+            .matches(
+                &fingerprint(
+                    "ANM: Cod galben de ploi în jumătate de țară până duminică",
+                    "https://other-news.ro/ploi"
+                ),
+                8
+            )
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_without_title_words() {
+        let now = Utc::now();
+        let stored: Fingerprint = serde_json::from_str(&format!(
+            r#"{{"url":null,"title":{},"seen_at":"2026-10-17T09:10:00Z"}}"#,
+            title_simhash("Guvernul a adoptat bugetul")
+        ))
+        .unwrap();
+        let post = NewsPost {
+            title: Some(String::from("Guvernul a adoptat bugetul")),
+            ..Default::default()
+        };
+
+        assert!(stored.title_words.is_empty());
+        assert!(!stored.matches(&Fingerprint::of(&post, now), 8));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_detector_in_memory() {
+        let now = Utc::now();
+        let post = NewsPost {
+            title: Some(String::from("Cod galben de ploi în jumătate de țară")),
+            link: Some(String::from("https://some-news.ro/ploi")),
+            ..Default::default()
+        };
+        let mut detector = DuplicateDetector::new(TimeDelta::hours(1), 8);
+
+        assert!(detector.find_duplicate(&post, now).is_none());
+        detector.remember(&post, now).await;
+
+        assert!(detector.find_duplicate(&post, now).is_some());
+        assert!(
+            detector
+                .find_duplicate(&post, now + TimeDelta::hours(2))
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::config::Source;
+use crate::dedup::DuplicateDetector;
+use crate::politeness::FetchSkipped;
+use crate::{ScrapeContext, scrape_source};
+use chrono::Utc;
+use futures::future::join_all;
+use log::{error, info};
+use post::NewsPost;
+use serde::Serialize;
+
+/// A post printed by the dry run.
+#[derive(Serialize, Debug, PartialEq)]
+struct DryRunPost<'a> {
+    /// The name of the post's source.
+    source: &'a str,
+    #[serde(flatten)]
+    post: &'a NewsPost,
+    /// Why the post would have been dropped, `None` if it would have been published.
+    dropped: Option<String>,
+}
+
+/// Scrapes the sources once and prints their posts to stdout as JSON lines, with the reason the post would have
+/// been dropped by the filters or the deduplication. Returns the number of sources which failed,
+/// the sources which may not be fetched, e.g. disallowed by robots.txt, are skipped and don't count.
+pub async fn run(
+    context: &ScrapeContext,
+    sources: &[Source],
+    mut duplicate_detector: DuplicateDetector,
+) -> usize {
+    let results = join_all(sources.iter().map(|source| scrape_source(context, source))).await;
+
+    let mut failed = 0;
+    for (source, result) in sources.iter().zip(results) {
+        let posts = match result {
+            Ok(run) => run.map(|run| run.posts).unwrap_or_default(),
+            Err(e) if e.is::<FetchSkipped>() => {
+                info!("Not scraping source {}: {}", source.name, e);
+                continue;
+            }
+            Err(e) => {
+                error!("Failed to get posts for source {}: {:?}", source.name, e);
+                failed += 1;
+                continue;
+            }
+        };
+        info!("Source {} returned {} posts", source.name, posts.len());
+
+        for (post, dropped) in posts {
+            let now = Utc::now();
+            let dropped = match dropped {
+                Some(reason) => Some(reason.to_string()),
+                None => match duplicate_detector.find_duplicate(&post, now) {
+                    Some(duplicate) => Some(format!(
+                        "duplicate of {}",
+                        duplicate.url.as_deref().unwrap_or("an earlier post")
+                    )),
+                    None => {
+                        duplicate_detector.remember(&post, now).await;
+                        None
+                    }
+                },
+            };
+            let line = DryRunPost {
+                source: &source.name,
+                post: &post,
+                dropped,
+            };
+            match serde_json::to_string(&line) {
+                Ok(line) => println!("{line}"),
+                Err(e) => error!("Failed to serialize post {:?}: {}", post, e),
+            }
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scraper::WebScrapperEngine;
+    use crate::targets::Feed;
+    use chrono::TimeDelta;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_run_skips_disallowed_sources() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /private\n"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/broken"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        let context = ScrapeContext {
+            engine: Arc::new(WebScrapperEngine::default()),
+            ..crate::tests::context(Duration::from_secs(5))
+        };
+        let source = |name: &str| {
+            Source::new(
+                name,
+                Feed::new(&format!("{}/{name}", mock_server.uri()), "Some News"),
+            )
+        };
+
+        // Test
+        let skipped = run(
+            &context,
+            &[source("private")],
+            DuplicateDetector::new(TimeDelta::hours(1), 0),
+        )
+        .await;
+        let failed = run(
+            &context,
+            &[source("private"), source("broken")],
+            DuplicateDetector::new(TimeDelta::hours(1), 0),
+        )
+        .await;
+
+        // Assert
+        assert_eq!(skipped, 0);
+        assert_eq!(failed, 1);
+    }
+
+    #[test]
+    fn test_dry_run_post_serialization() -> Result<(), anyhow::Error> {
+        let post = NewsPost {
+            title: Some(String::from("Horoscop")),
+            link: Some(String::from("https://some-news.ro/horoscop")),
+            author: Some(String::from("Some News")),
+            language: Some(String::from("ro")),
+            ..Default::default()
+        };
+        let line = DryRunPost {
+            source: "some-news",
+            post: &post,
+            dropped: Some(String::from("excluded by rule horoscope")),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&line)?,
+            r#"{"source":"some-news","image":null,"title":"Horoscop","summary":null,"link":"https://some-news.ro/horoscop","author":"Some News","published_at":null,"language":"ro","image_info":null,"body":null,"tags":[],"dropped":"excluded by rule horoscope"}"#
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,475 @@
+use crate::politeness::FetchSkipped;
+use crate::readability::extract_article_text;
+use crate::scraper::{WebScrapperEngine, parse_timestamp, resolve_url};
+use chrono_tz::Tz;
+use futures::StreamExt;
+use log::{debug, info, warn};
+use post::NewsPost;
+use scraper::{Html, Selector};
+use serde_json::Value;
+use std::time::Duration;
+
+/// The metadata found on an article page.
+#[derive(Debug, Default, PartialEq)]
+pub struct ArticleMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub author: Option<String>,
+    pub published_time: Option<String>,
+}
+
+impl ArticleMetadata {
+    /// Fills the fields which are missing from self with the ones from other.
+    fn or(self, other: ArticleMetadata) -> ArticleMetadata {
+        ArticleMetadata {
+            title: self.title.or(other.title),
+            description: self.description.or(other.description),
+            image: self.image.or(other.image),
+            author: self.author.or(other.author),
+            published_time: self.published_time.or(other.published_time),
+        }
+    }
+}
+
+/// Returns the trimmed string if it's not empty.
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Returns true if the field is missing or empty.
+fn is_missing(field: &Option<String>) -> bool {
+    field.as_deref().is_none_or(|f| f.trim().is_empty())
+}
+
+/// Reads the OpenGraph and article meta tags.
+fn parse_meta_tags(document: &Html) -> ArticleMetadata {
+    let mut metadata = ArticleMetadata::default();
+    let selector = Selector::parse("meta[property], meta[name]").expect("valid selector");
+
+    for element in document.select(&selector) {
+        let Some(property) = element.attr("property").or_else(|| element.attr("name")) else {
+            continue;
+        };
+        let Some(content) = element.attr("content").and_then(non_empty) else {
+            continue;
+        };
+        let field = match property {
+            "og:title" => &mut metadata.title,
+            "og:description" => &mut metadata.description,
+            "og:image" | "og:image:url" | "og:image:secure_url" => &mut metadata.image,
+            "article:author" => &mut metadata.author,
+            "article:published_time" => &mut metadata.published_time,
+            _ => continue,
+        };
+        if field.is_none() {
+            *field = Some(content);
+        }
+    }
+
+    metadata
+}
+
+/// Returns true if the JSON-LD node is a schema.org article.
+fn is_article(node: &Value) -> bool {
+    let is_article_type = |t: &Value| t.as_str().is_some_and(|t| t.ends_with("Article"));
+    match node.get("@type") {
+        Some(Value::Array(types)) => types.iter().any(is_article_type),
+        Some(t) => is_article_type(t),
+        None => false,
+    }
+}
+
+/// Extracts a text or a URL from a JSON-LD value, e.g. `"a"`, `{"name": "a"}`, `[{"url": "a"}]`.
+fn json_ld_text(value: &Value, key: &str) -> Option<String> {
+    match value {
+        Value::String(s) => non_empty(s),
+        Value::Object(o) => o.get(key).and_then(|v| json_ld_text(v, key)),
+        Value::Array(a) => {
+            let values: Vec<String> = a.iter().filter_map(|v| json_ld_text(v, key)).collect();
+            if values.is_empty() {
+                None
+            } else if key == "name" {
+                Some(values.join(", "))
+            } else {
+                values.into_iter().next()
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Finds the first article node in the JSON-LD document.
+fn find_article(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(nodes) => nodes.iter().find_map(find_article),
+        Value::Object(o) => {
+            if is_article(value) {
+                Some(value)
+            } else {
+                o.get("@graph").and_then(find_article)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Reads the schema.org NewsArticle JSON-LD.
+fn parse_json_ld(document: &Html) -> ArticleMetadata {
+    let selector = Selector::parse("script[type='application/ld+json']").expect("valid selector");
+
+    document
+        .select(&selector)
+        .filter_map(|script| serde_json::from_str::<Value>(&script.text().collect::<String>()).ok())
+        .find_map(|value| {
+            find_article(&value).map(|article| ArticleMetadata {
+                title: article
+                    .get("headline")
+                    .and_then(|v| json_ld_text(v, "name")),
+                description: article
+                    .get("description")
+                    .and_then(|v| json_ld_text(v, "name")),
+                image: article.get("image").and_then(|v| json_ld_text(v, "url")),
+                author: article.get("author").and_then(|v| json_ld_text(v, "name")),
+                published_time: article
+                    .get("datePublished")
+                    .and_then(|v| json_ld_text(v, "name")),
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Parses the article's metadata, OpenGraph tags take precedence over the JSON-LD.
+pub fn parse_article_metadata(html: &str) -> ArticleMetadata {
+    let document = Html::parse_document(html);
+    parse_meta_tags(&document).or(parse_json_ld(&document))
+}
+
+/// Fills the post's missing fields from the article metadata.
+pub fn fill_missing_fields(post: &mut NewsPost, metadata: ArticleMetadata) {
+    if is_missing(&post.title) && metadata.title.is_some() {
+        post.title = metadata.title;
+    }
+    if is_missing(&post.summary) && metadata.description.is_some() {
+        post.summary = metadata.description;
+    }
+    if is_missing(&post.image) && metadata.image.is_some() {
+        post.image = metadata.image;
+    }
+    if is_missing(&post.author) && metadata.author.is_some() {
+        post.author = metadata.author;
+    }
+    if post.published_at.is_none() {
+        post.published_at = metadata
+            .published_time
+            .as_deref()
+            .and_then(|time| parse_timestamp(time, Tz::UTC));
+    }
+}
+
+/// The article enricher fetches each post's article and fills in the missing fields.
+/// It can also extract the article's text into the post's body.
+pub struct ArticleEnricher {
+    concurrency: usize,
+    timeout: Duration,
+    fill_missing_fields: bool,
+    /// The maximum number of characters of the extracted body, `None` if no body is extracted.
+    body_max_chars: Option<usize>,
+}
+
+impl ArticleEnricher {
+    /// Creates a new enricher fetching at most `concurrency` articles at once,
+    /// each article fetch is abandoned after `timeout`.
+    pub fn new(concurrency: usize, timeout: Duration) -> Self {
+        ArticleEnricher {
+            concurrency: concurrency.max(1),
+            timeout,
+            fill_missing_fields: true,
+            body_max_chars: None,
+        }
+    }
+
+    /// Sets whether the missing fields are filled from the article's metadata.
+    pub fn with_missing_fields(mut self, fill_missing_fields: bool) -> Self {
+        self.fill_missing_fields = fill_missing_fields;
+        self
+    }
+
+    /// Extracts the article's text into the post's body, cut to at most `max_chars` characters.
+    pub fn with_body(mut self, max_chars: usize) -> Self {
+        self.body_max_chars = Some(max_chars);
+        self
+    }
+
+    /// Enriches a single post, the post is left unchanged if the article can't be fetched.
+    async fn enrich_post(&self, engine: &WebScrapperEngine, post: &mut NewsPost) {
+        let Some(link) = post.link.clone() else {
+            return;
+        };
+        let needs_fields = self.fill_missing_fields
+            && (is_missing(&post.title)
+                || is_missing(&post.summary)
+                || is_missing(&post.image)
+                || is_missing(&post.author));
+        let needs_body = self.body_max_chars.is_some() && is_missing(&post.body);
+        if !needs_fields && !needs_body {
+            return;
+        }
+
+        match engine.get_page(&link, self.timeout).await {
+            Ok(html) => {
+                debug!("Enriching post from {link}");
+                if needs_fields {
+                    let mut metadata = parse_article_metadata(&html);
+                    metadata.image = metadata.image.and_then(|i| resolve_url(&link, &i));
+                    fill_missing_fields(post, metadata);
+                    post.normalize();
+                }
+                if let Some(max_chars) = self.body_max_chars
+                    && needs_body
+                {
+                    post.body = extract_article_text(&html, max_chars);
+                }
+            }
+            Err(err) if err.is::<FetchSkipped>() => info!("Not enriching post: {err}"),
+            Err(err) => warn!("Failed to fetch article {link} for enrichment: {err}"),
+        }
+    }
+
+    /// Enriches the posts in place.
+    pub async fn enrich<'a>(
+        &self,
+        engine: &WebScrapperEngine,
+        posts: impl IntoIterator<Item = &'a mut NewsPost>,
+    ) {
+        let enrichments: Vec<_> = posts
+            .into_iter()
+            .map(|post| self.enrich_post(engine, post))
+            .collect();
+        futures::stream::iter(enrichments)
+            .buffer_unordered(self.concurrency)
+            .collect::<()>()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ARTICLE: &str = r#"
+<html>
+<head>
+  <meta property="og:title" content="The OpenGraph title"/>
+  <meta property="og:image" content="https://some-news.ro/og.jpg"/>
+  <meta property="article:author" content="Jane Doe"/>
+  <script type="application/ld+json">
+  {
+    "@context": "https://schema.org",
+    "@graph": [
+      {"@type": "WebPage", "name": "Some page"},
+      {
+        "@type": "NewsArticle",
+        "headline": "The JSON-LD headline",
+        "description": "The JSON-LD description",
+        "datePublished": "2026-10-17T12:10:00+03:00",
+        "image": [{"@type": "ImageObject", "url": "https://some-news.ro/ld.jpg"}],
+        "author": [{"@type": "Person", "name": "John Doe"}, {"@type": "Person", "name": "Jane Doe"}]
+      }
+    ]
+  }
+  </script>
+</head>
+<body></body>
+</html>"#;
+
+    #[test]
+    fn test_parse_article_metadata() {
+        let metadata = parse_article_metadata(ARTICLE);
+
+        assert_eq!(
+            metadata,
+            ArticleMetadata {
+                title: Some(String::from("The OpenGraph title")),
+                description: Some(String::from("The JSON-LD description")),
+                image: Some(String::from("https://some-news.ro/og.jpg")),
+                author: Some(String::from("Jane Doe")),
+                published_time: Some(String::from("2026-10-17T12:10:00+03:00")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_article_metadata_json_ld_only() {
+        let html = r#"<html><head><script type="application/ld+json">
+        {"@type": "NewsArticle", "headline": "Headline", "image": "https://some-news.ro/a.jpg",
+         "author": {"@type": "Person", "name": "John Doe"}}
+        </script></head></html>"#;
+
+        let metadata = parse_article_metadata(html);
+
+        assert_eq!(
+            metadata,
+            ArticleMetadata {
+                title: Some(String::from("Headline")),
+                description: None,
+                image: Some(String::from("https://some-news.ro/a.jpg")),
+                author: Some(String::from("John Doe")),
+                published_time: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_article_metadata_invalid_json_ld() {
+        let html =
+            r#"<html><head><script type="application/ld+json">{not json</script></head></html>"#;
+
+        assert_eq!(parse_article_metadata(html), ArticleMetadata::default());
+    }
+
+    #[test]
+    fn test_fill_missing_fields_keeps_existing() {
+        let mut post = NewsPost {
+            title: Some(String::from("Scraped title")),
+            summary: Some(String::from("")),
+            link: Some(String::from("https://some-news.ro/a")),
+            author: Some(String::from("Some News")),
+            ..Default::default()
+        };
+
+        fill_missing_fields(&mut post, parse_article_metadata(ARTICLE));
+
+        assert_eq!(
+            post,
+            NewsPost {
+                image: Some(String::from("https://some-news.ro/og.jpg")),
+                title: Some(String::from("Scraped title")),
+                summary: Some(String::from("The JSON-LD description")),
+                link: Some(String::from("https://some-news.ro/a")),
+                author: Some(String::from("Some News")),
+                published_at: Some(Utc.with_ymd_and_hms(2026, 10, 17, 9, 10, 0).unwrap()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enrich_fetches_articles() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(ARTICLE))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let mut posts = vec![
+            NewsPost {
+                title: Some(String::from("Title")),
+                link: Some(format!("{}/article", mock_server.uri())),
+                ..Default::default()
+            },
+            NewsPost {
+                title: Some(String::from("No link")),
+                ..Default::default()
+            },
+        ];
+        let enricher = ArticleEnricher::new(2, Duration::from_secs(5));
+
+        // Test
+        enricher
+            .enrich(&WebScrapperEngine::default(), &mut posts)
+            .await;
+
+        // Assert
+        assert_eq!(
+            posts[0].summary,
+            Some(String::from("The JSON-LD description"))
+        );
+        assert_eq!(
+            posts[0].image,
+            Some(String::from("https://some-news.ro/og.jpg"))
+        );
+        assert_eq!(posts[0].author, Some(String::from("Jane Doe")));
+        assert_eq!(posts[1].title, Some(String::from("No link")));
+        assert_eq!(posts[1].summary, None);
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_enrich_extracts_body() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><body><nav><p>Home, politics, sports, weather and more.</p></nav>
+                <article><p>The article's first paragraph, which tells the whole story.</p></article>
+                </body></html>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let mut posts = vec![NewsPost {
+            title: Some(String::from("Title")),
+            link: Some(format!("{}/article", mock_server.uri())),
+            ..Default::default()
+        }];
+        let enricher = ArticleEnricher::new(1, Duration::from_secs(5))
+            .with_missing_fields(false)
+            .with_body(31);
+
+        // Test
+        enricher
+            .enrich(&WebScrapperEngine::default(), &mut posts)
+            .await;
+
+        // Assert
+        assert_eq!(
+            posts[0].body,
+            Some(String::from("The article's first paragraph,…"))
+        );
+        assert_eq!(posts[0].summary, None);
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_enrich_times_out() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(ARTICLE)
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&mock_server)
+            .await;
+        let post = NewsPost {
+            title: Some(String::from("Title")),
+            link: Some(format!("{}/slow", mock_server.uri())),
+            ..Default::default()
+        };
+        let enricher = ArticleEnricher::new(1, Duration::from_millis(100));
+
+        let mut posts = vec![post.clone()];
+
+        // Test
+        enricher
+            .enrich(&WebScrapperEngine::default(), &mut posts)
+            .await;
+
+        // Assert
+        assert_eq!(posts, vec![post]);
+    }
+}
@@ -0,0 +1,334 @@
+use anyhow::{Context, anyhow};
+use post::NewsPost;
+use regex::Regex;
+use serde::Deserialize;
+use url::Url;
+
+/// What happens to the posts matched by a rule.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    /// Only the posts matched by at least one include rule are kept.
+    Include,
+    /// The posts matched by the rule are dropped.
+    Exclude,
+}
+
+/// A post field matched by a rule.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostField {
+    Title,
+    Summary,
+    /// The path of the post's link.
+    Link,
+    Author,
+}
+
+impl PostField {
+    fn all() -> Vec<PostField> {
+        vec![
+            PostField::Title,
+            PostField::Summary,
+            PostField::Link,
+            PostField::Author,
+        ]
+    }
+
+    /// Returns the field's value from the post.
+    fn value(&self, post: &NewsPost) -> Option<String> {
+        match self {
+            PostField::Title => post.title.clone(),
+            PostField::Summary => post.summary.clone(),
+            PostField::Link => post
+                .link
+                .as_deref()
+                .map(|link| Url::parse(link).map_or(link.to_string(), |u| u.path().to_string())),
+            PostField::Author => post.author.clone(),
+        }
+    }
+}
+
+/// A filter rule as written in the configuration file.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FilterRuleConfig {
+    /// The name of the rule, logged when it drops a post.
+    pub name: String,
+    pub action: FilterAction,
+    /// The fields matched by the rule, defaults to all of them.
+    #[serde(default = "PostField::all")]
+    pub fields: Vec<PostField>,
+    /// Words or phrases matched as whole words, ignoring case and diacritics.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// A regular expression, use `(?i)` to ignore the case.
+    pub regex: Option<String>,
+}
+
+/// Lowercases the text, removes its diacritics and keeps its words separated by single spaces.
+pub(crate) fn normalize_words(text: &str) -> String {
+    deunicode::deunicode(text)
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A rule matching posts by keywords or a regular expression.
+#[derive(Debug, Clone)]
+pub struct FilterRule {
+    name: String,
+    action: FilterAction,
+    fields: Vec<PostField>,
+    keywords: Vec<String>,
+    regex: Option<Regex>,
+}
+
+impl FilterRule {
+    /// Returns a description of the match if the rule matches one of the post's fields.
+    fn find_match(&self, post: &NewsPost) -> Option<String> {
+        self.fields.iter().find_map(|field| {
+            let value = field.value(post)?;
+            if let Some(regex) = &self.regex
+                && let Some(found) = regex.find(&value)
+            {
+                return Some(format!("{field:?} matches `{}`", found.as_str()));
+            }
+            let words = format!(" {} ", normalize_words(&value));
+            self.keywords
+                .iter()
+                .find(|keyword| words.contains(&format!(" {keyword} ")))
+                .map(|keyword| format!("{field:?} contains `{keyword}`"))
+        })
+    }
+}
+
+impl TryFrom<&FilterRuleConfig> for FilterRule {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &FilterRuleConfig) -> Result<Self, Self::Error> {
+        let keywords: Vec<String> = value
+            .keywords
+            .iter()
+            .map(|k| normalize_words(k))
+            .filter(|k| !k.is_empty())
+            .collect();
+        if keywords.is_empty() && value.regex.is_none() {
+            return Err(anyhow!(
+                "filter rule {} needs keywords or a regex",
+                value.name
+            ));
+        }
+        if value.fields.is_empty() {
+            return Err(anyhow!(
+                "filter rule {} needs at least one field",
+                value.name
+            ));
+        }
+        let regex = value
+            .regex
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .with_context(|| format!("invalid regex for filter rule {}", value.name))?;
+
+        Ok(FilterRule {
+            name: value.name.clone(),
+            action: value.action,
+            fields: value.fields.clone(),
+            keywords,
+            regex,
+        })
+    }
+}
+
+/// Decides which posts are kept, from a set of include and exclude rules.
+#[derive(Debug, Clone, Default)]
+pub struct PostFilter {
+    rules: Vec<FilterRule>,
+}
+
+impl PostFilter {
+    /// Builds the filter from the rules' configuration.
+    pub fn new(rules: &[FilterRuleConfig]) -> Result<Self, anyhow::Error> {
+        Ok(PostFilter {
+            rules: rules
+                .iter()
+                .map(FilterRule::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Returns the reason the post is dropped, or `None` if it's kept.
+    /// A post is dropped if an exclude rule matches it, or if there are include rules and none matches it.
+    pub fn check(&self, post: &NewsPost) -> Option<String> {
+        let excluded = self
+            .rules
+            .iter()
+            .filter(|r| r.action == FilterAction::Exclude)
+            .find_map(|r| r.find_match(post).map(|m| (r, m)));
+        if let Some((rule, found)) = excluded {
+            return Some(format!("excluded by rule {}: {found}", rule.name));
+        }
+
+        let include_rules: Vec<&FilterRule> = self
+            .rules
+            .iter()
+            .filter(|r| r.action == FilterAction::Include)
+            .collect();
+        if !include_rules.is_empty() && include_rules.iter().all(|r| r.find_match(post).is_none()) {
+            let names: Vec<&str> = include_rules.iter().map(|r| r.name.as_str()).collect();
+            return Some(format!(
+                "not matched by the include rules {}",
+                names.join(", ")
+            ));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(title: &str, link: &str) -> NewsPost {
+        NewsPost {
+            title: Some(title.to_string()),
+            summary: Some(String::from("Un rezumat")),
+            link: Some(link.to_string()),
+            author: Some(String::from("Redacția")),
+            ..Default::default()
+        }
+    }
+
+    fn rule(name: &str, action: FilterAction, fields: Vec<PostField>) -> FilterRuleConfig {
+        FilterRuleConfig {
+            name: name.to_string(),
+            action,
+            fields,
+            keywords: vec![],
+            regex: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_keywords_match_whole_words() -> Result<(), anyhow::Error> {
+        let mut horoscope = rule("horoscope", FilterAction::Exclude, PostField::all());
+        horoscope.keywords = vec![String::from("Horoscop"), String::from("zodia berbec")];
+        let filter = PostFilter::new(&[horoscope])?;
+
+        assert_eq!(
+            filter.check(&post(
+                "HOROSCOP 17 octombrie",
+                "https://some-news.ro/lifestyle/1"
+            )),
+            Some(String::from(
+                "excluded by rule horoscope: Title contains `horoscop`"
+            ))
+        );
+        assert!(
+            filter
+                .check(&post(
+                    "Ce aduce ziua pentru Zodia Berbec",
+                    "https://some-news.ro/1"
+                ))
+                .is_some()
+        );
+        assert!(
+            filter
+                .check(&post("Horoscopul lunii", "https://some-news.ro/1"))
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_regex_on_link_path() -> Result<(), anyhow::Error> {
+        let mut sports = rule("sports", FilterAction::Exclude, vec![PostField::Link]);
+        sports.regex = Some(String::from("^/(sport|fotbal)/"));
+        let filter = PostFilter::new(&[sports])?;
+
+        assert_eq!(
+            filter.check(&post(
+                "Meciul naționalei",
+                "https://some-news.ro/sport/meci"
+            )),
+            Some(String::from(
+                "excluded by rule sports: Link matches `/sport/`"
+            ))
+        );
+        assert!(
+            filter
+                .check(&post(
+                    "Sport la Parlament",
+                    "https://some-news.ro/politic/sport"
+                ))
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_include_rules() -> Result<(), anyhow::Error> {
+        let mut politics = rule("politics", FilterAction::Include, vec![PostField::Link]);
+        politics.regex = Some(String::from("^/politic"));
+        let mut economy = rule("economy", FilterAction::Include, vec![PostField::Link]);
+        economy.keywords = vec![String::from("economie")];
+        let mut sponsored = rule("sponsored", FilterAction::Exclude, vec![PostField::Title]);
+        sponsored.regex = Some(String::from("(?i)\\b(advertorial|sponsorizat)\\b"));
+        let filter = PostFilter::new(&[politics, economy, sponsored])?;
+
+        assert!(
+            filter
+                .check(&post("Votul final", "https://some-news.ro/politic/vot"))
+                .is_none()
+        );
+        assert!(
+            filter
+                .check(&post("Inflația", "https://some-news.ro/economie/inflatie"))
+                .is_none()
+        );
+        assert_eq!(
+            filter.check(&post("Cod galben", "https://some-news.ro/meteo/cod")),
+            Some(String::from(
+                "not matched by the include rules politics, economy"
+            ))
+        );
+        assert_eq!(
+            filter.check(&post(
+                "Advertorial: o bancă nouă",
+                "https://some-news.ro/economie/banca"
+            )),
+            Some(String::from(
+                "excluded by rule sponsored: Title matches `Advertorial`"
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_without_rules_keeps_everything() {
+        let filter = PostFilter::default();
+
+        assert!(
+            filter
+                .check(&post("Titlu", "https://some-news.ro/1"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_invalid_filter_rules() {
+        let empty = rule("empty", FilterAction::Exclude, PostField::all());
+        let mut invalid_regex = rule("invalid", FilterAction::Exclude, PostField::all());
+        invalid_regex.regex = Some(String::from("(unclosed"));
+        let mut no_fields = rule("no-fields", FilterAction::Exclude, vec![]);
+        no_fields.keywords = vec![String::from("sport")];
+
+        assert!(PostFilter::new(&[empty]).is_err());
+        assert!(PostFilter::new(&[invalid_regex]).is_err());
+        assert!(PostFilter::new(&[no_fields]).is_err());
+    }
+}
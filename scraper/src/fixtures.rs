@@ -0,0 +1,55 @@
+//! Offline tests for the scraper targets.
+//!
+//! Every target has a snapshot of its page in `fixtures/snapshots` and the posts expected from it
+//! in `fixtures/golden`. The snapshots are refreshed from the live sites with
+//! `make refresh-scraper-fixtures`, which also regenerates the golden output.
+use crate::scraper::{ScrapableWebPage, WebScrapperEngine, clean_post};
+use post::NewsPost;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// When set, the golden output is rewritten instead of being checked.
+const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+/// Checks the posts parsed from the snapshot, cleaned up like the engine does, against the golden output.
+pub(crate) fn assert_golden<P: ScrapableWebPage + ?Sized>(
+    page: &P,
+    snapshot: &str,
+    golden: &str,
+) -> Result<(), anyhow::Error> {
+    let html = std::fs::read_to_string(fixtures_dir().join("snapshots").join(snapshot))?;
+    let golden_path = fixtures_dir().join("golden").join(golden);
+
+    let mut posts = page.get_posts(html)?;
+    for post in posts.iter_mut() {
+        clean_post(&page.get_url(), post);
+    }
+
+    if std::env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+        std::fs::write(&golden_path, serde_json::to_string_pretty(&posts)? + "\n")?;
+        return Ok(());
+    }
+
+    let expected: Vec<NewsPost> = serde_json::from_str(&std::fs::read_to_string(&golden_path)?)?;
+    assert_eq!(
+        posts, expected,
+        "posts parsed from {snapshot} differ from {golden}, run with {UPDATE_GOLDEN_ENV}=1 to update it"
+    );
+    Ok(())
+}
+
+/// Replaces the snapshot with the page currently served by the live site.
+pub(crate) async fn refresh_snapshot<P: ScrapableWebPage + ?Sized>(
+    page: &P,
+    snapshot: &str,
+) -> Result<(), anyhow::Error> {
+    let html = WebScrapperEngine::default()
+        .get_page(&page.get_url(), Duration::from_secs(30))
+        .await?;
+    std::fs::write(fixtures_dir().join("snapshots").join(snapshot), html)?;
+    Ok(())
+}
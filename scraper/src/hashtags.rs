@@ -0,0 +1,168 @@
+use crate::filters::normalize_words;
+use post::NewsPost;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// The default maximum number of hashtags of a post.
+fn default_max_tags() -> usize {
+    3
+}
+
+/// The hashtags configuration, shared by all the sources.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HashtagsConfig {
+    /// The maximum number of hashtags of a post.
+    #[serde(default = "default_max_tags")]
+    pub max_tags: usize,
+    /// The tags of the posts whose title or summary contains the keyword, matched as whole words
+    /// ignoring case and diacritics, e.g. `guvernul = "Guvern"`.
+    #[serde(default)]
+    pub keywords: BTreeMap<String, String>,
+}
+
+impl Default for HashtagsConfig {
+    fn default() -> Self {
+        HashtagsConfig {
+            max_tags: default_max_tags(),
+            keywords: BTreeMap::new(),
+        }
+    }
+}
+
+/// Turns the text into a hashtag without the `#`: the diacritics are folded and
+/// everything but letters, digits and underscores is removed, e.g. `Știri externe` becomes `Stiriexterne`.
+pub fn to_hashtag(text: &str) -> String {
+    deunicode::deunicode(text)
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect()
+}
+
+/// Derives the hashtags of the posts from their title and summary.
+#[derive(Debug, Clone, Default)]
+pub struct HashtagGenerator {
+    max_tags: usize,
+    /// The normalized keywords with their tags.
+    keywords: Vec<(String, String)>,
+    default_tags: Vec<String>,
+}
+
+impl HashtagGenerator {
+    /// Creates a new generator from the configuration, the source's default tags follow the matched ones.
+    pub fn new(config: &HashtagsConfig, default_tags: &[String]) -> Self {
+        HashtagGenerator {
+            max_tags: config.max_tags,
+            keywords: config
+                .keywords
+                .iter()
+                .map(|(keyword, tag)| (normalize_words(keyword), to_hashtag(tag)))
+                .filter(|(keyword, tag)| !keyword.is_empty() && !tag.is_empty())
+                .collect(),
+            default_tags: default_tags.iter().map(|t| to_hashtag(t)).collect(),
+        }
+    }
+
+    /// Returns at most `max_tags` tags for the post: the tags of the keywords found in its title and summary,
+    /// in the order they appear, then the source's default tags. Duplicates are removed ignoring the case.
+    pub fn tags(&self, post: &NewsPost) -> Vec<String> {
+        let text = [post.title.as_deref(), post.summary.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(normalize_words)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let text = format!(" {text} ");
+
+        let mut matched: Vec<(usize, &String)> = self
+            .keywords
+            .iter()
+            .filter_map(|(keyword, tag)| Some((text.find(&format!(" {keyword} "))?, tag)))
+            .collect();
+        matched.sort_by_key(|(position, _)| *position);
+
+        let mut tags: Vec<String> = vec![];
+        for tag in matched
+            .into_iter()
+            .map(|(_, tag)| tag)
+            .chain(&self.default_tags)
+        {
+            if tags.len() >= self.max_tags {
+                break;
+            }
+            if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(title: &str, summary: Option<&str>) -> NewsPost {
+        NewsPost {
+            title: Some(title.to_string()),
+            summary: summary.map(|s| s.to_string()),
+            link: Some(String::from("https://some-news.ro/stire")),
+            ..Default::default()
+        }
+    }
+
+    fn config(max_tags: usize) -> HashtagsConfig {
+        HashtagsConfig {
+            max_tags,
+            keywords: BTreeMap::from([
+                (String::from("guvernul"), String::from("Guvern")),
+                (String::from("Parlamentul"), String::from("Parlament")),
+                (String::from("inflația"), String::from("Economie")),
+                (String::from("prețuri"), String::from("Economie")),
+                (String::from("București"), String::from("București")),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_to_hashtag() {
+        assert_eq!(to_hashtag("Știri externe"), "Stiriexterne");
+        assert_eq!(to_hashtag("Țară-Mamă"), "TaraMama");
+        assert_eq!(to_hashtag("covid_19"), "covid_19");
+        assert_eq!(to_hashtag("!!"), "");
+    }
+
+    #[test]
+    fn test_tags() {
+        let generator = HashtagGenerator::new(&config(3), &[String::from("știri")]);
+
+        assert_eq!(
+            generator.tags(&post(
+                "Inflația crește, Guvernul anunță măsuri",
+                Some("Prețurile din BUCUREȘTI")
+            )),
+            vec!["Economie", "Guvern", "Bucuresti"]
+        );
+        // The keywords are whole words, the default tags fill the rest.
+        assert_eq!(
+            generator.tags(&post("Parlamentul votează", Some("Guvernului i se cere"))),
+            vec!["Parlament", "stiri"]
+        );
+        assert_eq!(generator.tags(&post("Vremea", None)), vec!["stiri"]);
+    }
+
+    #[test]
+    fn test_tags_limit() {
+        let generator = HashtagGenerator::new(&config(1), &[String::from("stiri")]);
+
+        assert_eq!(
+            generator.tags(&post("Guvernul și Parlamentul", None)),
+            vec!["Guvern"]
+        );
+        assert!(
+            HashtagGenerator::default()
+                .tags(&post("Guvernul", None))
+                .is_empty()
+        );
+    }
+}
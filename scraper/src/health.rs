@@ -0,0 +1,376 @@
+use chrono::{DateTime, Utc};
+use infrastructure::RedisService;
+use log::{info, warn};
+use post::NewsPost;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// The health of a source is kept in Redis for 30 days after its last run.
+const SOURCE_HEALTH_TTL: u64 = 60 * 60 * 24 * 30;
+
+/// The number of runs needed before a source is compared against its baseline.
+const MIN_BASELINE_RUNS: usize = 3;
+
+/// Counts below this baseline average are too small to be compared.
+const MIN_BASELINE_AVERAGE: f64 = 1.0;
+
+/// The number of runs failing in a row after which a source is degraded.
+const MAX_CONSECUTIVE_FAILURES: usize = 3;
+
+/// The statistics of one scrape of a source.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrapeStats {
+    /// The posts returned by the target.
+    pub found: usize,
+    /// The posts with a title and a link.
+    pub complete: usize,
+    /// The posts with an image.
+    pub with_image: usize,
+    /// The posts with a non-empty summary.
+    pub with_summary: usize,
+}
+
+impl ScrapeStats {
+    /// Computes the statistics of the posts returned by a target.
+    pub fn from_posts(posts: &[NewsPost]) -> Self {
+        ScrapeStats {
+            found: posts.len(),
+            complete: posts.iter().filter(|p| p.is_complete()).count(),
+            with_image: posts.iter().filter(|p| p.image.is_some()).count(),
+            with_summary: posts
+                .iter()
+                .filter(|p| p.summary.as_ref().is_some_and(|s| !s.trim().is_empty()))
+                .count(),
+        }
+    }
+
+    fn counts(&self) -> [(&'static str, usize); 4] {
+        [
+            ("found", self.found),
+            ("complete", self.complete),
+            ("with_image", self.with_image),
+            ("with_summary", self.with_summary),
+        ]
+    }
+}
+
+/// The health of a source, computed from its recent runs.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SourceHealth {
+    /// The time of the last recorded run.
+    pub last_run: Option<DateTime<Utc>>,
+    /// The statistics of the last recorded run.
+    pub last_stats: ScrapeStats,
+    /// The statistics of the recent healthy runs, oldest first.
+    pub baseline: VecDeque<ScrapeStats>,
+    /// True if the last run's counts dropped sharply against the baseline.
+    pub degraded: bool,
+    /// Explains why the source is degraded.
+    pub degraded_reasons: Vec<String>,
+    /// The number of runs which failed in a row, reset by a successful run.
+    #[serde(default)]
+    pub consecutive_failures: usize,
+}
+
+impl SourceHealth {
+    /// Records a run and updates the degraded state.
+    /// Degraded runs are kept out of the baseline, so the source stays degraded until its counts recover.
+    fn record(
+        &mut self,
+        stats: ScrapeStats,
+        now: DateTime<Utc>,
+        window: usize,
+        drop_threshold: f64,
+    ) {
+        self.degraded_reasons = if self.baseline.len() >= MIN_BASELINE_RUNS {
+            self.compare(&stats, drop_threshold)
+        } else {
+            vec![]
+        };
+        self.degraded = !self.degraded_reasons.is_empty();
+        self.last_run = Some(now);
+        self.last_stats = stats;
+        self.consecutive_failures = 0;
+
+        if !self.degraded {
+            self.baseline.push_back(stats);
+            while self.baseline.len() > window {
+                self.baseline.pop_front();
+            }
+        }
+    }
+
+    /// Records a run which failed with the error, e.g. a fetch error or a timeout.
+    /// The source is degraded once too many runs failed in a row, its baseline is kept for when it recovers.
+    fn record_failure(&mut self, error: &str, now: DateTime<Utc>) {
+        self.consecutive_failures += 1;
+        self.last_run = Some(now);
+        self.last_stats = ScrapeStats::default();
+        if self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+            self.degraded = true;
+            self.degraded_reasons = vec![format!(
+                "failed {} runs in a row, the last one with: {error}",
+                self.consecutive_failures
+            )];
+        }
+    }
+
+    /// Returns the counts which dropped by more than the threshold against the baseline average.
+    fn compare(&self, stats: &ScrapeStats, drop_threshold: f64) -> Vec<String> {
+        let runs = self.baseline.len() as f64;
+        stats
+            .counts()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (name, count))| {
+                let average =
+                    self.baseline.iter().map(|s| s.counts()[i].1).sum::<usize>() as f64 / runs;
+                (average >= MIN_BASELINE_AVERAGE
+                    && (*count as f64) < average * (1.0 - drop_threshold))
+                    .then(|| format!("{name} dropped to {count} from an average of {average:.1}"))
+            })
+            .collect()
+    }
+}
+
+/// Tracks the statistics of every source and flags the degraded ones.
+pub struct HealthMonitor {
+    sources: Mutex<HashMap<String, SourceHealth>>,
+    store: Option<RedisService>,
+    window: usize,
+    drop_threshold: f64,
+}
+
+impl HealthMonitor {
+    /// Creates a monitor comparing each run against the average of the last `window` healthy runs.
+    /// A source is degraded when a count drops by more than `drop_threshold`, a fraction between 0 and 1.
+    pub fn new(window: usize, drop_threshold: f64) -> Self {
+        HealthMonitor {
+            sources: Mutex::new(HashMap::new()),
+            store: None,
+            window: window.max(1),
+            drop_threshold: drop_threshold.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Keeps the health of the sources in Redis so it survives restarts and can be read by operators.
+    pub fn with_store(mut self, redis_service: RedisService) -> Self {
+        self.store = Some(redis_service);
+        self
+    }
+
+    /// Returns the Redis key holding the health of the source.
+    pub fn health_key(source: &str) -> String {
+        format!("scraper:health:{source}")
+    }
+
+    /// Returns the health of the source.
+    pub async fn get(&self, source: &str) -> SourceHealth {
+        let cached = self.sources.lock().unwrap().get(source).cloned();
+        if let Some(health) = cached {
+            return health;
+        }
+
+        let Some(mut store) = self.store.clone() else {
+            return SourceHealth::default();
+        };
+        match store
+            .get_value::<SourceHealth>(&Self::health_key(source))
+            .await
+        {
+            Ok(health) => health.unwrap_or_default(),
+            Err(err) => {
+                warn!("Failed to load the health of source {source}: {err}");
+                SourceHealth::default()
+            }
+        }
+    }
+
+    /// Records the statistics of a run of the source and returns its updated health.
+    pub async fn record(&self, source: &str, stats: ScrapeStats) -> SourceHealth {
+        self.update(source, |health, now| {
+            health.record(stats, now, self.window, self.drop_threshold)
+        })
+        .await
+    }
+
+    /// Records a failed run of the source and returns its updated health.
+    pub async fn record_failure(&self, source: &str, error: &str) -> SourceHealth {
+        self.update(source, |health, now| health.record_failure(error, now))
+            .await
+    }
+
+    /// Updates the health of the source with a run, logs its changes and saves it.
+    async fn update(
+        &self,
+        source: &str,
+        record: impl FnOnce(&mut SourceHealth, DateTime<Utc>),
+    ) -> SourceHealth {
+        let mut health = self.get(source).await;
+        let was_degraded = health.degraded;
+        record(&mut health, Utc::now());
+
+        if health.degraded {
+            warn!(
+                "Source {source} is degraded, its target may need updating: {}",
+                health.degraded_reasons.join(", ")
+            );
+        } else if was_degraded {
+            info!("Source {source} has recovered");
+        }
+
+        self.sources
+            .lock()
+            .unwrap()
+            .insert(source.to_string(), health.clone());
+        if let Some(mut store) = self.store.clone()
+            && let Err(err) = store
+                .set_value(&Self::health_key(source), &health, Some(SOURCE_HEALTH_TTL))
+                .await
+        {
+            warn!("Failed to save the health of source {source}: {err}");
+        }
+        health
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(found: usize, complete: usize, with_image: usize, with_summary: usize) -> ScrapeStats {
+        ScrapeStats {
+            found,
+            complete,
+            with_image,
+            with_summary,
+        }
+    }
+
+    #[test]
+    fn test_scrape_stats_from_posts() {
+        let posts = vec![
+            NewsPost {
+                image: Some(String::from("https://some-news.ro/1.jpg")),
+                title: Some(String::from("First")),
+                summary: Some(String::from("Summary")),
+                link: Some(String::from("https://some-news.ro/1")),
+                ..Default::default()
+            },
+            NewsPost {
+                title: Some(String::from("Second")),
+                summary: Some(String::from(" ")),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(ScrapeStats::from_posts(&posts), stats(2, 1, 1, 1));
+    }
+
+    #[test]
+    fn test_source_health_needs_a_baseline() {
+        let mut health = SourceHealth::default();
+        let now = Utc::now();
+
+        health.record(stats(20, 20, 20, 20), now, 10, 0.5);
+        health.record(stats(20, 20, 20, 20), now, 10, 0.5);
+        health.record(stats(0, 0, 0, 0), now, 10, 0.5);
+
+        assert!(!health.degraded);
+        assert_eq!(health.baseline.len(), 3);
+    }
+
+    #[test]
+    fn test_source_health_degraded_on_sharp_drop() {
+        let mut health = SourceHealth::default();
+        let now = Utc::now();
+        for _ in 0..5 {
+            health.record(stats(20, 20, 18, 0), now, 10, 0.5);
+        }
+
+        health.record(stats(20, 20, 15, 0), now, 10, 0.5);
+        assert!(!health.degraded);
+
+        health.record(stats(20, 20, 2, 0), now, 10, 0.5);
+        assert!(health.degraded);
+        assert_eq!(health.degraded_reasons.len(), 1);
+        assert!(health.degraded_reasons[0].starts_with("with_image dropped to 2"));
+        assert_eq!(health.baseline.len(), 6);
+
+        health.record(stats(20, 20, 17, 0), now, 10, 0.5);
+        assert!(!health.degraded);
+        assert!(health.degraded_reasons.is_empty());
+    }
+
+    #[test]
+    fn test_source_health_baseline_window() {
+        let mut health = SourceHealth::default();
+        let now = Utc::now();
+        for found in 1..=5 {
+            health.record(stats(found, 0, 0, 0), now, 3, 0.9);
+        }
+
+        assert_eq!(
+            health.baseline,
+            VecDeque::from([stats(3, 0, 0, 0), stats(4, 0, 0, 0), stats(5, 0, 0, 0)])
+        );
+        assert_eq!(health.last_stats, stats(5, 0, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_record() {
+        let monitor = HealthMonitor::new(10, 0.5);
+        for _ in 0..3 {
+            monitor.record("some-news", stats(10, 10, 10, 10)).await;
+        }
+
+        let health = monitor.record("some-news", stats(0, 0, 0, 0)).await;
+
+        assert!(health.degraded);
+        assert_eq!(health.degraded_reasons.len(), 4);
+        assert_eq!(monitor.get("some-news").await, health);
+        assert_eq!(monitor.get("other-news").await, SourceHealth::default());
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_record_failures() {
+        let monitor = HealthMonitor::new(10, 0.5);
+        for _ in 0..3 {
+            monitor.record("some-news", stats(10, 10, 10, 10)).await;
+        }
+
+        // A source failing since its first run is flagged too.
+        for _ in 0..2 {
+            assert!(
+                !monitor
+                    .record_failure("some-news", "timed out")
+                    .await
+                    .degraded
+            );
+            assert!(
+                !monitor
+                    .record_failure("other-news", "HTTP 500")
+                    .await
+                    .degraded
+            );
+        }
+        let health = monitor.record_failure("some-news", "timed out").await;
+        let other_health = monitor.record_failure("other-news", "HTTP 500").await;
+
+        assert!(health.degraded);
+        assert_eq!(health.consecutive_failures, 3);
+        assert_eq!(
+            health.degraded_reasons,
+            vec![String::from(
+                "failed 3 runs in a row, the last one with: timed out"
+            )]
+        );
+        assert_eq!(health.baseline.len(), 3);
+        assert!(other_health.degraded);
+
+        let health = monitor.record("some-news", stats(10, 10, 10, 10)).await;
+        assert!(!health.degraded);
+        assert_eq!(health.consecutive_failures, 0);
+    }
+}
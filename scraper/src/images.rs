@@ -0,0 +1,285 @@
+use crate::politeness::FetchSkipped;
+use crate::scraper::{ResourceHead, WebScrapperEngine};
+use futures::StreamExt;
+use imagesize::ImageType;
+use log::{debug, info};
+use post::{ImageInfo, NewsPost};
+use std::time::Duration;
+
+/// The number of bytes read from the start of an image to find its type and dimensions.
+const IMAGE_HEAD_BYTES: usize = 64 * 1024;
+
+/// The limits a post's image must be within to be published.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageLimits {
+    /// The maximum size of the image in bytes.
+    pub max_bytes: u64,
+    /// The minimum width of the image in pixels, smaller images are usually icons or tracking pixels.
+    pub min_width: usize,
+    /// The minimum height of the image in pixels.
+    pub min_height: usize,
+}
+
+/// Returns the MIME type of the image types the bots can publish.
+fn publishable_mime_type(image_type: ImageType) -> Option<&'static str> {
+    match image_type {
+        ImageType::Jpeg => Some("image/jpeg"),
+        ImageType::Png => Some("image/png"),
+        ImageType::Gif => Some("image/gif"),
+        ImageType::Webp => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Checks the start of a fetched image against the limits.
+/// Returns the image's info or the reason it's invalid.
+pub fn check_image(head: &ResourceHead, limits: &ImageLimits) -> Result<ImageInfo, String> {
+    if let Some(content_type) = &head.content_type
+        && (!content_type.starts_with("image/") || content_type == "image/svg+xml")
+    {
+        return Err(format!("unsupported content type {content_type}"));
+    }
+    if head.size > limits.max_bytes {
+        return Err(format!(
+            "{} bytes is over the limit of {} bytes",
+            head.size, limits.max_bytes
+        ));
+    }
+
+    // The type is read from the bytes, the Content-Type of images is often wrong.
+    let mime_type = imagesize::image_type(&head.bytes)
+        .ok()
+        .and_then(publishable_mime_type)
+        .ok_or_else(|| String::from("not a JPEG, PNG, GIF or WebP image"))?;
+    let size = imagesize::blob_size(&head.bytes)
+        .map_err(|err| format!("can't read the dimensions: {err}"))?;
+    if size.width < limits.min_width || size.height < limits.min_height {
+        return Err(format!(
+            "{}x{} pixels is smaller than {}x{} pixels",
+            size.width, size.height, limits.min_width, limits.min_height
+        ));
+    }
+
+    Ok(ImageInfo {
+        mime_type: mime_type.to_string(),
+        size: head.size,
+    })
+}
+
+/// The image validator fetches the start of each post's image and checks it's publishable.
+/// Invalid images are replaced by the fallback image, or removed when there's none.
+pub struct ImageValidator {
+    limits: ImageLimits,
+    fallback_image: Option<String>,
+    concurrency: usize,
+    timeout: Duration,
+}
+
+impl ImageValidator {
+    /// Creates a new validator fetching at most `concurrency` images at once,
+    /// each image fetch is abandoned after `timeout`.
+    pub fn new(limits: ImageLimits, concurrency: usize, timeout: Duration) -> Self {
+        ImageValidator {
+            limits,
+            fallback_image: None,
+            concurrency: concurrency.max(1),
+            timeout,
+        }
+    }
+
+    /// Sets the URL of the image replacing the invalid ones, it is not validated.
+    pub fn with_fallback_image(mut self, fallback_image: Option<String>) -> Self {
+        self.fallback_image = fallback_image;
+        self
+    }
+
+    /// Validates the post's image, annotating it with its info when it's valid.
+    /// Images which may not be fetched are kept as they are.
+    async fn validate_post(&self, engine: &WebScrapperEngine, post: &mut NewsPost) {
+        post.image_info = None;
+        let Some(image) = post.image.clone() else {
+            return;
+        };
+
+        let result = match engine
+            .get_head(
+                &image,
+                IMAGE_HEAD_BYTES,
+                self.limits.max_bytes,
+                self.timeout,
+            )
+            .await
+        {
+            Ok(head) => check_image(&head, &self.limits),
+            Err(err) if err.is::<FetchSkipped>() => {
+                info!("Not validating image: {err}");
+                return;
+            }
+            Err(err) => Err(format!("{err:#}")),
+        };
+        match result {
+            Ok(image_info) => {
+                debug!(
+                    "Image {image} is a {} of {} bytes",
+                    image_info.mime_type, image_info.size
+                );
+                post.image_info = Some(image_info);
+            }
+            Err(reason) => {
+                info!(
+                    "Invalid image {image} of post {}: {reason}",
+                    post.link.as_deref().unwrap_or_default()
+                );
+                post.image = self.fallback_image.clone();
+            }
+        }
+    }
+
+    /// Validates the images of the posts.
+    pub async fn validate<'a>(
+        &self,
+        engine: &WebScrapperEngine,
+        posts: impl IntoIterator<Item = &'a mut NewsPost>,
+    ) {
+        let validations: Vec<_> = posts
+            .into_iter()
+            .map(|post| self.validate_post(engine, post))
+            .collect();
+        futures::stream::iter(validations)
+            .buffer_unordered(self.concurrency)
+            .collect::<()>()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// The start of a PNG: the signature and the IHDR chunk with its dimensions.
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    fn limits() -> ImageLimits {
+        ImageLimits {
+            max_bytes: 1000,
+            min_width: 200,
+            min_height: 100,
+        }
+    }
+
+    fn head(content_type: Option<&str>, bytes: Vec<u8>) -> ResourceHead {
+        ResourceHead {
+            content_type: content_type.map(|c| c.to_string()),
+            size: bytes.len() as u64,
+            bytes,
+        }
+    }
+
+    #[test]
+    fn test_check_image() {
+        assert_eq!(
+            check_image(&head(Some("image/png"), png(640, 480)), &limits()),
+            Ok(ImageInfo {
+                mime_type: String::from("image/png"),
+                size: 29,
+            })
+        );
+        // The type comes from the bytes.
+        assert_eq!(
+            check_image(&head(None, png(640, 480)), &limits()).map(|i| i.mime_type),
+            Ok(String::from("image/png"))
+        );
+        assert_eq!(
+            check_image(&head(Some("image/png"), png(1, 1)), &limits()),
+            Err(String::from("1x1 pixels is smaller than 200x100 pixels"))
+        );
+        assert_eq!(
+            check_image(&head(Some("text/html"), png(640, 480)), &limits()),
+            Err(String::from("unsupported content type text/html"))
+        );
+        assert_eq!(
+            check_image(
+                &head(Some("image/svg+xml"), b"<svg></svg>".to_vec()),
+                &limits()
+            ),
+            Err(String::from("unsupported content type image/svg+xml"))
+        );
+        assert_eq!(
+            check_image(
+                &head(Some("image/jpeg"), b"not an image".to_vec()),
+                &limits()
+            ),
+            Err(String::from("not a JPEG, PNG, GIF or WebP image"))
+        );
+
+        let mut large = head(Some("image/png"), png(640, 480));
+        large.size = 5000;
+        assert_eq!(
+            check_image(&large, &limits()),
+            Err(String::from("5000 bytes is over the limit of 1000 bytes"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_images() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/large.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(png(640, 480), "image/png"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pixel.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(png(1, 1), "image/png"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing.png"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let post = |image: Option<&str>| NewsPost {
+            image: image.map(|i| format!("{}{i}", mock_server.uri())),
+            title: Some(String::from("Titlu")),
+            link: Some(String::from("https://some-news.ro/titlu")),
+            ..Default::default()
+        };
+        let mut posts = [
+            post(Some("/large.png")),
+            post(Some("/pixel.png")),
+            post(Some("/missing.png")),
+            post(None),
+        ];
+        let engine = WebScrapperEngine::default().with_robots_txt(false);
+        let fallback_image = String::from("https://some-news.ro/logo.png");
+        let validator = ImageValidator::new(limits(), 2, Duration::from_secs(5))
+            .with_fallback_image(Some(fallback_image.clone()));
+
+        validator.validate(&engine, posts.iter_mut()).await;
+
+        assert_eq!(
+            posts[0].image,
+            Some(format!("{}/large.png", mock_server.uri()))
+        );
+        assert_eq!(
+            posts[0].image_info,
+            Some(ImageInfo {
+                mime_type: String::from("image/png"),
+                size: 29,
+            })
+        );
+        assert_eq!(posts[1].image, Some(fallback_image.clone()));
+        assert_eq!(posts[1].image_info, None);
+        assert_eq!(posts[2].image, Some(fallback_image));
+        assert_eq!(posts[3].image, None);
+    }
+}
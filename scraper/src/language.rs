@@ -0,0 +1,168 @@
+use post::NewsPost;
+use whatlang::Lang;
+
+/// Returns the ISO 639-1 code of the language.
+fn iso_639_1(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Epo => "eo",
+        Lang::Eng => "en",
+        Lang::Rus => "ru",
+        Lang::Cmn => "zh",
+        Lang::Spa => "es",
+        Lang::Por => "pt",
+        Lang::Ita => "it",
+        Lang::Ben => "bn",
+        Lang::Fra => "fr",
+        Lang::Deu => "de",
+        Lang::Ukr => "uk",
+        Lang::Kat => "ka",
+        Lang::Ara => "ar",
+        Lang::Hin => "hi",
+        Lang::Jpn => "ja",
+        Lang::Heb => "he",
+        Lang::Yid => "yi",
+        Lang::Pol => "pl",
+        Lang::Amh => "am",
+        Lang::Jav => "jv",
+        Lang::Kor => "ko",
+        Lang::Nob => "nb",
+        Lang::Dan => "da",
+        Lang::Swe => "sv",
+        Lang::Fin => "fi",
+        Lang::Tur => "tr",
+        Lang::Nld => "nl",
+        Lang::Hun => "hu",
+        Lang::Ces => "cs",
+        Lang::Ell => "el",
+        Lang::Bul => "bg",
+        Lang::Bel => "be",
+        Lang::Mar => "mr",
+        Lang::Kan => "kn",
+        Lang::Ron => "ro",
+        Lang::Slv => "sl",
+        Lang::Hrv => "hr",
+        Lang::Srp => "sr",
+        Lang::Mkd => "mk",
+        Lang::Lit => "lt",
+        Lang::Lav => "lv",
+        Lang::Est => "et",
+        Lang::Tam => "ta",
+        Lang::Vie => "vi",
+        Lang::Urd => "ur",
+        Lang::Tha => "th",
+        Lang::Guj => "gu",
+        Lang::Uzb => "uz",
+        Lang::Pan => "pa",
+        Lang::Aze => "az",
+        Lang::Ind => "id",
+        Lang::Tel => "te",
+        Lang::Pes => "fa",
+        Lang::Mal => "ml",
+        Lang::Ori => "or",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Sin => "si",
+        Lang::Khm => "km",
+        Lang::Tuk => "tk",
+        Lang::Aka => "ak",
+        Lang::Zul => "zu",
+        Lang::Sna => "sn",
+        Lang::Afr => "af",
+        Lang::Lat => "la",
+        Lang::Slk => "sk",
+        Lang::Cat => "ca",
+        Lang::Tgl => "tl",
+        Lang::Hye => "hy",
+    }
+}
+
+/// Returns the lowercase primary language of a language tag such as `ro-RO` or `en_US`.
+pub fn language_code(tag: &str) -> Option<String> {
+    let primary = tag.trim().split(['-', '_']).next()?.to_lowercase();
+    ((2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic()))
+        .then_some(primary)
+}
+
+/// Detects the language of the post's title and summary, offline.
+/// Returns the ISO 639-1 code of the language, or `None` if the detection's confidence, between 0 and 1,
+/// is below `min_confidence`.
+pub fn detect_language(post: &NewsPost, min_confidence: f64) -> Option<String> {
+    let text = [post.title.as_deref(), post.summary.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(". ");
+    whatlang::detect(&text)
+        .filter(|info| info.confidence() >= min_confidence)
+        .map(|info| iso_639_1(info.lang()).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(title: &str, summary: Option<&str>) -> NewsPost {
+        NewsPost {
+            title: Some(title.to_string()),
+            summary: summary.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_language_code() {
+        assert_eq!(language_code("ro-RO"), Some(String::from("ro")));
+        assert_eq!(language_code(" en_US "), Some(String::from("en")));
+        assert_eq!(language_code("FR"), Some(String::from("fr")));
+        assert_eq!(language_code(""), None);
+        assert_eq!(language_code("romanian"), None);
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language(
+                &post(
+                    "Parlamentul a votat legea pensiilor în forma finală",
+                    Some("Legea a trecut de votul final al Camerei Deputaților cu 210 voturi")
+                ),
+                0.15
+            ),
+            Some(String::from("ro"))
+        );
+        assert_eq!(
+            detect_language(
+                &post(
+                    "Romania's parliament passes the final pension law",
+                    Some("The law passed the final vote in the Chamber of Deputies.")
+                ),
+                0.15
+            ),
+            Some(String::from("en"))
+        );
+        assert_eq!(
+            detect_language(
+                &post("Le gouvernement adopte le projet de budget", None),
+                0.15
+            ),
+            Some(String::from("fr"))
+        );
+    }
+
+    #[test]
+    fn test_detect_language_low_confidence() {
+        // Romanian without diacritics is mistaken for Spanish, with a low confidence.
+        let post = post("Cod galben de ploi in jumatate de tara pana duminica", None);
+
+        assert_eq!(detect_language(&post, 0.15), None);
+        assert!(detect_language(&post, 0.0).is_some());
+    }
+
+    #[test]
+    fn test_detect_language_without_text() {
+        let mut post = post("", None);
+        post.title = None;
+
+        assert_eq!(detect_language(&post, 0.0), None);
+    }
+}
@@ -1,22 +1,149 @@
+use crate::admin::{AdminState, ScrapeRequest};
 use crate::cli::CliArgs;
-use crate::scraper::{ScrapableWebPage, WebScrapperEngine};
-use crate::targets::{GFourMedia, HotNews};
+use crate::config::{ScraperConfig, Source};
+use crate::dedup::DuplicateDetector;
+use crate::enrichment::ArticleEnricher;
+use crate::health::{HealthMonitor, ScrapeStats};
+use crate::images::{ImageLimits, ImageValidator};
+use crate::language::detect_language;
+use crate::politeness::FetchSkipped;
+use crate::schedule::Schedule;
+use crate::scraper::WebScrapperEngine;
+use crate::seen::{LinkChanges, SeenLinks, seen_link};
+use crate::status::{RunPost, RunStatus, SourceStatuses};
+use crate::targets::{Feed, GFourMedia, HotNews};
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, TimeDelta, Utc};
 use clap::Parser;
-use clokwerk::{AsyncScheduler, Interval, TimeUnits};
 use infrastructure::RedisService;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use post::NewsPost;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, watch};
+use tokio::sync::{Semaphore, mpsc, watch};
 use tokio::task::JoinHandle;
 
+mod admin;
 mod cli;
+mod config;
+mod dedup;
+mod dry_run;
+mod enrichment;
+mod filters;
+#[cfg(test)]
+mod fixtures;
+mod hashtags;
+mod health;
+mod images;
+mod language;
+mod politeness;
+mod readability;
+mod schedule;
 mod scraper;
+mod seen;
+mod status;
 mod targets;
 
-/// Runs the scheduler in a background task until shutdown is requested.
+/// Is shared by the scrape jobs of all the sources.
+#[derive(Clone)]
+struct ScrapeContext {
+    engine: Arc<WebScrapperEngine>,
+    max_posts: u64,
+    enricher: Option<Arc<ArticleEnricher>>,
+    image_validator: Option<Arc<ImageValidator>>,
+    health: Arc<HealthMonitor>,
+    /// Posts published longer ago are dropped.
+    max_post_age: Option<TimeDelta>,
+    /// The minimum confidence of the language detection.
+    language_min_confidence: f64,
+    /// Limits the number of sources scraped at once.
+    scrape_slots: Arc<Semaphore>,
+    /// The deadline of the sources without their own.
+    source_timeout: Duration,
+    /// The runtime status of the sources, also used to disable them.
+    statuses: Arc<SourceStatuses>,
+    /// The links seen on the previous run of each source.
+    seen_links: Arc<SeenLinks>,
+}
+
+/// A source scraped on its own schedule.
+struct ScrapeJob {
+    source: Arc<Source>,
+    schedule: Schedule,
+    next_run: Option<DateTime<Utc>>,
+    running: Option<JoinHandle<()>>,
+}
+
+impl ScrapeJob {
+    /// Creates the job of a source, sources without a schedule use the default one.
+    fn new(source: Source, default_schedule: &Schedule, now: DateTime<Utc>) -> Self {
+        let schedule = source
+            .schedule
+            .clone()
+            .unwrap_or_else(|| default_schedule.clone());
+        let next_run = schedule.next_run(now);
+        match next_run {
+            Some(next_run) => info!("Source {} will first run at {}", source.name, next_run),
+            None => warn!("Source {} is never scheduled to run", source.name),
+        }
+        ScrapeJob {
+            source: Arc::new(source),
+            schedule,
+            next_run,
+            running: None,
+        }
+    }
+
+    /// Starts the job if it is due and its source is enabled.
+    fn run_if_due(
+        &mut self,
+        context: &ScrapeContext,
+        tx: &mpsc::Sender<NewsPost>,
+        now: DateTime<Utc>,
+    ) {
+        if self.next_run.is_none_or(|next_run| next_run > now) {
+            return;
+        }
+        self.next_run = self.schedule.next_run(now);
+
+        if !context.statuses.is_enabled(&self.source.name) {
+            debug!(
+                "Source {} is disabled, skipping this run.",
+                self.source.name
+            );
+            return;
+        }
+        self.run(context, tx);
+    }
+
+    /// Starts the job, a job whose previous run hasn't finished is skipped.
+    fn run(&mut self, context: &ScrapeContext, tx: &mpsc::Sender<NewsPost>) {
+        if self.running.as_ref().is_some_and(|r| !r.is_finished()) {
+            warn!(
+                "Previous scrape job for {} is still running, skipping this run.",
+                self.source.name
+            );
+            return;
+        }
+
+        info!("Running the scrapping job for {}.", self.source.name);
+        let source = self.source.clone();
+        let context = context.clone();
+        let tx = tx.clone();
+        self.running = Some(tokio::spawn(async move {
+            scrape_and_send(&context, &tx, &source).await;
+        }));
+    }
+}
+
+/// Runs the scrape jobs in a background task until shutdown is requested.
 fn run_scheduler(
-    mut scheduler: AsyncScheduler,
+    mut jobs: Vec<ScrapeJob>,
+    context: ScrapeContext,
+    tx: mpsc::Sender<NewsPost>,
+    mut scrape_rx: mpsc::Receiver<ScrapeRequest>,
     mut shutdown_rx: watch::Receiver<bool>,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
@@ -30,63 +157,242 @@ fn run_scheduler(
                     }
                 }
                 _ = ticker.tick() => {
-                    scheduler.run_pending().await;
+                    let now = Utc::now();
+                    for job in jobs.iter_mut() {
+                        job.run_if_due(&context, &tx, now);
+                    }
+                }
+                Some(request) = scrape_rx.recv() => {
+                    info!("Scrape requested: {:?}", request);
+                    for job in jobs.iter_mut() {
+                        let requested = match &request {
+                            ScrapeRequest::All => context.statuses.is_enabled(&job.source.name),
+                            ScrapeRequest::Source(name) => &job.source.name == name,
+                        };
+                        if requested {
+                            job.run(&context, &tx);
+                        }
+                    }
                 }
             }
         }
     })
 }
 
-async fn scrape_and_send<S>(
-    engine: &WebScrapperEngine,
-    source: S,
-    tx: &mpsc::Sender<NewsPost>,
-    max_posts: u64,
-) where
-    S: ScrapableWebPage + Default,
-{
-    match engine.get_posts(source).await {
-        Ok(posts) => {
-            for p in posts
-                .iter()
-                .filter(|p| p.is_complete())
-                .take(max_posts as usize)
-            {
-                if tx.send(p.clone()).await.is_err() {
-                    error!("Receiver has been dropped. Could not send post: {:?}", p);
+/// Why a scraped post is not sent for publishing.
+#[derive(Debug, Clone, PartialEq)]
+enum DropReason {
+    /// It was already seen on the source's previous run.
+    Unchanged,
+    /// It was published longer ago than the maximum post age.
+    Stale,
+    /// A filter rule dropped it.
+    Filtered(String),
+    /// It misses some of its fields.
+    Incomplete,
+    /// The source already has the maximum number of posts for this run.
+    OverLimit,
+}
+
+impl fmt::Display for DropReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DropReason::Unchanged => write!(f, "seen on the previous run"),
+            DropReason::Stale => write!(f, "stale"),
+            DropReason::Filtered(reason) => write!(f, "{reason}"),
+            DropReason::Incomplete => write!(f, "incomplete"),
+            DropReason::OverLimit => write!(f, "over the limit of posts per run"),
+        }
+    }
+}
+
+/// Enriches the new posts of the source and decides which of them are sent for publishing,
+/// then validates the images of the sent ones.
+async fn prepare_posts(
+    context: &ScrapeContext,
+    source: &Source,
+    mut posts: Vec<NewsPost>,
+    previous_links: &HashSet<String>,
+) -> Vec<(NewsPost, Option<DropReason>)> {
+    let max_posts = context.max_posts as usize;
+    let now = Utc::now();
+    let is_unchanged =
+        |p: &NewsPost| seen_link(p).is_some_and(|link| previous_links.contains(&link));
+    let is_fresh = |p: &NewsPost| {
+        context
+            .max_post_age
+            .is_none_or(|max_age| !p.is_stale(max_age, now))
+    };
+
+    if let Some(enricher) = &context.enricher {
+        // Posts without a title can still be completed from their article, the already seen
+        // and stale ones aren't fetched again.
+        let candidates = posts.iter_mut().filter(|p| is_fresh(p) && !is_unchanged(p));
+        enricher.enrich(&context.engine, candidates).await;
+    }
+
+    let mut kept = 0;
+    let mut posts: Vec<_> = posts
+        .into_iter()
+        .map(|mut p| {
+            let dropped = if is_unchanged(&p) {
+                Some(DropReason::Unchanged)
+            } else if !is_fresh(&p) {
+                Some(DropReason::Stale)
+            } else if let Some(reason) = source.filter.check(&p) {
+                Some(DropReason::Filtered(reason))
+            } else if !p.is_complete() {
+                Some(DropReason::Incomplete)
+            } else if kept >= max_posts {
+                Some(DropReason::OverLimit)
+            } else {
+                None
+            };
+            if dropped.is_none() {
+                kept += 1;
+                if p.language.is_none() {
+                    p.language = detect_language(&p, context.language_min_confidence);
                 }
+                p.tags = source.hashtags.tags(&p);
             }
-        }
-        Err(e) => {
-            error!(
-                "Failed to get posts for source {}: {:?}",
-                std::any::type_name::<S>(),
-                e
-            );
-        }
+            (p, dropped)
+        })
+        .collect();
+
+    if let Some(image_validator) = &context.image_validator {
+        let kept = posts
+            .iter_mut()
+            .filter(|(_, dropped)| dropped.is_none())
+            .map(|(p, _)| p);
+        image_validator.validate(&context.engine, kept).await;
     }
-    info!("Scrape job finished for {}", std::any::type_name::<S>())
+    posts
 }
 
-/// Runs the scraping job at the specified interval.
-fn run_scrapping_job(
-    scheduler: &mut AsyncScheduler,
-    tx: mpsc::Sender<NewsPost>,
-    interval: Interval,
-    max_posts: u64,
-) {
-    scheduler.every(interval).run(move || {
-        let tx = tx.clone();
-        info!("Running the scrapping job.");
-        async move {
-            let engine: WebScrapperEngine = WebScrapperEngine::default();
+/// The posts of a source's run.
+struct SourceRun {
+    /// The posts with the reason they're not sent for publishing, if any.
+    posts: Vec<(NewsPost, Option<DropReason>)>,
+    /// How the posts changed since the previous run.
+    changes: LinkChanges,
+}
+
+/// Scrapes the source and prepares its posts, waiting for a free scrape slot first.
+/// The links of the posts are remembered by the caller, once it knows which posts were handed off.
+/// The scrape is abandoned once the source's deadline passes, returns `None` if the source has not changed.
+async fn scrape_source(
+    context: &ScrapeContext,
+    source: &Source,
+) -> Result<Option<SourceRun>, anyhow::Error> {
+    let _permit = context.scrape_slots.acquire().await?;
+    let deadline = source.timeout.unwrap_or(context.source_timeout);
+
+    let scrape = async {
+        let Some(posts) = context.engine.get_posts(source.page.as_ref()).await? else {
+            return Ok(None);
+        };
+        context
+            .health
+            .record(&source.name, ScrapeStats::from_posts(&posts))
+            .await;
 
-            tokio::join!(
-                scrape_and_send::<HotNews>(&engine, HotNews::default(), &tx, max_posts),
-                scrape_and_send::<GFourMedia>(&engine, GFourMedia::default(), &tx, max_posts)
+        let previous_links = context.seen_links.previous(&source.name).await;
+        let changes = LinkChanges::between(&previous_links, &posts);
+        let posts = prepare_posts(context, source, posts, &previous_links).await;
+        Ok(Some(SourceRun { posts, changes }))
+    };
+    tokio::time::timeout(deadline, scrape)
+        .await
+        .map_err(|_| anyhow!("timed out after {} seconds", deadline.as_secs_f64()))?
+}
+
+/// Scrapes the source and sends its kept posts for publishing.
+/// The links of the sent posts and of the posts dropped for good are remembered, the other posts,
+/// e.g. incomplete or over the limit, are new again on the next run.
+async fn scrape_and_send(context: &ScrapeContext, tx: &mpsc::Sender<NewsPost>, source: &Source) {
+    context.statuses.start_run(&source.name);
+    let (run_status, run_posts, changes) = match scrape_source(context, source).await {
+        Ok(None) => {
+            info!("Source {} has not changed since the last run", source.name);
+            (RunStatus::NotModified, None, None)
+        }
+        Ok(Some(SourceRun { posts, changes })) => {
+            info!(
+                "Source {} has {} new, {} unchanged and {} gone posts",
+                source.name, changes.new, changes.unchanged, changes.gone
             );
+            let mut stale = 0;
+            let mut run_posts = vec![];
+            let mut links = HashSet::new();
+            let mut kept = vec![];
+            for (p, dropped) in posts {
+                run_posts.push(RunPost {
+                    post: p.clone(),
+                    dropped: dropped.as_ref().map(|reason| reason.to_string()),
+                });
+                let link = seen_link(&p);
+                match dropped {
+                    None => {
+                        links.extend(link);
+                        kept.push(p);
+                    }
+                    Some(DropReason::Unchanged) => links.extend(link),
+                    Some(DropReason::Stale) => {
+                        stale += 1;
+                        links.extend(link);
+                    }
+                    Some(reason @ DropReason::Filtered(_)) => {
+                        info!(
+                            "Dropped post {:?} from source {}, {}",
+                            p.link, source.name, reason
+                        );
+                        links.extend(link);
+                    }
+                    Some(reason) => {
+                        debug!(
+                            "Dropped post {:?} from source {}, {}",
+                            p.link, source.name, reason
+                        );
+                    }
+                }
+            }
+            if stale > 0 {
+                info!("Dropped {} stale posts from source {}", stale, source.name);
+            }
+            // Remembered before sending, so a post failing to publish while the others are still
+            // being sent is forgotten.
+            context.seen_links.remember(&source.name, links).await;
+            for p in kept {
+                let link = seen_link(&p);
+                if let Err(err) = tx.send(p).await {
+                    error!(
+                        "Receiver has been dropped. Could not send post: {:?}",
+                        err.0
+                    );
+                    if let Some(link) = link {
+                        context.seen_links.forget(&link).await;
+                    }
+                }
+            }
+            (RunStatus::Succeeded, Some(run_posts), Some(changes))
         }
-    });
+        Err(e) if e.is::<FetchSkipped>() => {
+            info!("Not scraping source {}: {}", source.name, e);
+            (RunStatus::Skipped(e.to_string()), None, None)
+        }
+        Err(e) => {
+            error!("Failed to get posts for source {}: {:?}", source.name, e);
+            context
+                .health
+                .record_failure(&source.name, &format!("{e:#}"))
+                .await;
+            (RunStatus::Failed(format!("{e:#}")), None, None)
+        }
+    };
+    context
+        .statuses
+        .finish_run(&source.name, run_status, run_posts, changes);
+    info!("Scrape job finished for {}", source.name)
 }
 
 #[tokio::main]
@@ -95,10 +401,115 @@ async fn main() -> Result<(), anyhow::Error> {
     let args = CliArgs::parse();
     info!("Starting the program");
 
-    let mut redis_service = RedisService::new(&args.redis_connection_string).await;
-    let mut scheduler = AsyncScheduler::new();
-    let (tx, mut rx) = mpsc::channel::<NewsPost>(256);
+    let mut sources = match &args.config_file {
+        Some(config_file) => ScraperConfig::from_file(config_file)?.build_sources()?,
+        None => vec![
+            Source::new("hotnews", HotNews::default()),
+            Source::new("g4media", GFourMedia::default()),
+        ],
+    };
+    sources.extend(
+        args.feeds
+            .iter()
+            .map(|f| Source::new(&f.url, Feed::new(&f.url, &f.default_author))),
+    );
+    if !args.only_sources.is_empty() {
+        if let Some(unknown) = args
+            .only_sources
+            .iter()
+            .find(|name| !sources.iter().any(|s| &s.name == *name))
+        {
+            bail!("unknown source: {unknown}");
+        }
+        sources.retain(|s| args.only_sources.contains(&s.name));
+    }
+    info!(
+        "Scraping {} sources: {:?}",
+        sources.len(),
+        sources.iter().map(|s| &s.name).collect::<Vec<_>>()
+    );
+
+    let redis_service = match &args.redis_connection_string {
+        Some(connection_string) if !args.dry_run => {
+            Some(RedisService::new(connection_string).await)
+        }
+        _ => None,
+    };
+
+    let mut engine = WebScrapperEngine::default()
+        .with_timeouts(
+            Duration::from_secs(args.connect_timeout_seconds),
+            Duration::from_secs(args.read_timeout_seconds),
+        )
+        .with_robots_txt(!args.ignore_robots_txt)
+        .with_min_host_delay(Duration::from_millis(args.min_host_delay_millis));
+    let mut health = HealthMonitor::new(args.health_window_runs, args.health_drop_threshold);
+    let mut seen_links = SeenLinks::default();
+    if let Some(redis_service) = &redis_service {
+        if args.persist_http_cache {
+            engine = engine.with_cache_store(redis_service.clone());
+        }
+        health = health.with_store(redis_service.clone());
+        seen_links = seen_links.with_store(redis_service.clone());
+    }
 
+    let context = ScrapeContext {
+        engine: Arc::new(engine),
+        max_posts: args.max_posts_per_run,
+        enricher: (args.enrich_posts || args.extract_body).then(|| {
+            let mut enricher = ArticleEnricher::new(
+                args.enrichment_concurrency,
+                Duration::from_secs(args.enrichment_timeout_seconds),
+            )
+            .with_missing_fields(args.enrich_posts);
+            if args.extract_body {
+                enricher = enricher.with_body(args.body_max_chars);
+            }
+            Arc::new(enricher)
+        }),
+        image_validator: args.validate_images.then(|| {
+            let limits = ImageLimits {
+                max_bytes: args.image_max_bytes,
+                min_width: args.image_min_width,
+                min_height: args.image_min_height,
+            };
+            Arc::new(
+                ImageValidator::new(
+                    limits,
+                    args.image_validation_concurrency,
+                    Duration::from_secs(args.image_validation_timeout_seconds),
+                )
+                .with_fallback_image(args.fallback_image_url.clone()),
+            )
+        }),
+        health: Arc::new(health),
+        max_post_age: args
+            .max_post_age_hours
+            .map(|hours| TimeDelta::hours(hours as i64)),
+        language_min_confidence: args.language_min_confidence,
+        scrape_slots: Arc::new(Semaphore::new(args.max_concurrent_sources as usize)),
+        source_timeout: Duration::from_secs(args.source_timeout_seconds),
+        statuses: Arc::new(SourceStatuses::new(sources.iter().map(|s| s.name.as_str()))),
+        seen_links: Arc::new(seen_links),
+    };
+    let duplicate_window = TimeDelta::hours(args.duplicate_window_hours as i64);
+
+    if args.dry_run {
+        let duplicate_detector =
+            DuplicateDetector::new(duplicate_window, args.duplicate_title_distance);
+        let failed = dry_run::run(&context, &sources, duplicate_detector).await;
+        if failed > 0 {
+            bail!("{failed} of {} sources failed", sources.len());
+        }
+        return Ok(());
+    }
+    let (Some(mut redis_service), Some(redis_stream_name)) =
+        (redis_service, args.redis_stream_name.as_deref())
+    else {
+        bail!("--redis-connection-string and --redis-stream-name are required");
+    };
+
+    let (tx, mut rx) = mpsc::channel::<NewsPost>(256);
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
     tokio::spawn({
         let shutdown_tx = shutdown_tx.clone();
@@ -111,15 +522,42 @@ async fn main() -> Result<(), anyhow::Error> {
             }
         }
     });
+    let default_schedule = Schedule::every(Duration::from_secs(
+        args.scrape_interval_minutes as u64 * 60,
+    ));
+    let now = Utc::now();
+    let jobs = sources
+        .into_iter()
+        .map(|source| ScrapeJob::new(source, &default_schedule, now))
+        .collect();
 
-    run_scrapping_job(
-        &mut scheduler,
-        tx,
-        args.scrape_interval_minutes.minutes(),
-        args.max_posts_per_run,
-    );
+    let mut duplicate_detector = DuplicateDetector::load(
+        redis_service.clone(),
+        redis_stream_name,
+        duplicate_window,
+        args.duplicate_title_distance,
+    )
+    .await;
 
-    let handle = run_scheduler(scheduler, shutdown_rx.clone());
+    let (scrape_tx, scrape_rx) = mpsc::channel::<ScrapeRequest>(16);
+    let admin_handle = match args.admin_address {
+        Some(address) => {
+            let listener = tokio::net::TcpListener::bind(address).await?;
+            let state = AdminState {
+                statuses: context.statuses.clone(),
+                scrape_tx: scrape_tx.clone(),
+            };
+            Some(tokio::spawn(admin::serve(
+                listener,
+                state,
+                shutdown_rx.clone(),
+            )))
+        }
+        None => None,
+    };
+
+    let seen_links = context.seen_links.clone();
+    let handle = run_scheduler(jobs, context, tx, scrape_rx, shutdown_rx.clone());
     let mut main_shutdown_rx = shutdown_rx;
 
     loop {
@@ -139,15 +577,25 @@ async fn main() -> Result<(), anyhow::Error> {
                 info!("Received post {:?}", news_post);
                 if news_post.is_complete() {
                     let title = news_post.title.clone().unwrap();
-                    let unique_post_key = format!("{}-{}", &args.redis_stream_name, &title);
+                    let unique_post_key = format!("{}-{}", redis_stream_name, &title);
                     let digest = format!("{:x}", md5::compute(unique_post_key));
-                    if !redis_service.is_key_flagged(&digest).await {
+                    let now = Utc::now();
+                    if let Some(duplicate) = duplicate_detector.find_duplicate(&news_post, now) {
+                        info!(
+                            "Skipping near-duplicate of the post published at {}: {:?}",
+                            duplicate.seen_at, news_post
+                        );
+                    } else if !redis_service.is_key_flagged(&digest).await {
                         let published = redis_service
-                            .publish(&args.redis_stream_name, &news_post)
+                            .publish(redis_stream_name, &news_post)
                             .await;
                         if published {
                             info!("Published {:?}", news_post);
                             redis_service.flag_key(&digest, 60 * 60 * 24 * 90).await;
+                            duplicate_detector.remember(&news_post, now).await;
+                        } else if let Some(link) = seen_link(&news_post) {
+                            // Offer the post again on the next run of its source.
+                            seen_links.forget(&link).await;
                         }
                     };
                 }
@@ -159,6 +607,308 @@ async fn main() -> Result<(), anyhow::Error> {
 
     let _ = shutdown_tx.send(true);
     handle.await?;
+    if let Some(admin_handle) = admin_handle {
+        admin_handle.await??;
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    pub(crate) fn context(source_timeout: Duration) -> ScrapeContext {
+        ScrapeContext {
+            engine: Arc::new(WebScrapperEngine::default().with_robots_txt(false)),
+            max_posts: 100,
+            enricher: None,
+            image_validator: None,
+            health: Arc::new(HealthMonitor::new(10, 0.5)),
+            max_post_age: None,
+            language_min_confidence: 0.15,
+            scrape_slots: Arc::new(Semaphore::new(1)),
+            source_timeout,
+            statuses: Arc::new(SourceStatuses::default()),
+            seen_links: Arc::new(SeenLinks::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scrape_source_times_out() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/fast"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<rss version=\"2.0\"><channel></channel></rss>"),
+            )
+            .mount(&mock_server)
+            .await;
+        let context = context(Duration::from_millis(200));
+        let slow = Source::new(
+            "slow",
+            Feed::new(&format!("{}/slow", mock_server.uri()), "Slow"),
+        );
+        let fast = Source::new(
+            "fast",
+            Feed::new(&format!("{}/fast", mock_server.uri()), "Fast"),
+        );
+
+        // Test
+        let (slow_result, fast_result) = tokio::join!(
+            scrape_source(&context, &slow),
+            scrape_source(&context, &fast)
+        );
+
+        // Assert
+        assert_eq!(
+            slow_result.err().map(|e| e.to_string()),
+            Some(String::from("timed out after 0.2 seconds"))
+        );
+        assert_eq!(fast_result?.map(|run| run.posts), Some(vec![]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_runs_degrade_the_source() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        let context = context(Duration::from_secs(5));
+        let source = Source::new(
+            "feed",
+            Feed::new(&format!("{}/feed", mock_server.uri()), "Feed"),
+        );
+        let (tx, _rx) = mpsc::channel(10);
+
+        // Test
+        for _ in 0..3 {
+            scrape_and_send(&context, &tx, &source).await;
+        }
+
+        // Assert
+        let health = context.health.get("feed").await;
+        assert!(health.degraded);
+        assert_eq!(health.consecutive_failures, 3);
+    }
+
+    fn rss(links: &[&str]) -> String {
+        let items: String = links
+            .iter()
+            .map(|link| format!("<item><title>Title of {link}</title><link>{link}</link></item>"))
+            .collect();
+        format!("<rss version=\"2.0\"><channel>{items}</channel></rss>")
+    }
+
+    #[tokio::test]
+    async fn test_scrape_source_drops_unchanged_posts() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(rss(&["https://some-news.ro/a", "https://some-news.ro/b"])),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(rss(&[
+                "https://some-news.ro/a?utm_source=rss",
+                "https://some-news.ro/c",
+            ])))
+            .mount(&mock_server)
+            .await;
+        let context = context(Duration::from_secs(5));
+        let source = Source::new(
+            "feed",
+            Feed::new(&format!("{}/feed", mock_server.uri()), "Feed"),
+        );
+
+        let (tx, _rx) = mpsc::channel(10);
+
+        // Test
+        scrape_and_send(&context, &tx, &source).await;
+        let second_run = scrape_source(&context, &source).await?.unwrap();
+
+        // Assert
+        assert_eq!(
+            context.statuses.get("feed").and_then(|s| s.changes),
+            Some(LinkChanges {
+                new: 2,
+                unchanged: 0,
+                gone: 0
+            })
+        );
+        assert_eq!(
+            second_run.changes,
+            LinkChanges {
+                new: 1,
+                unchanged: 1,
+                gone: 1
+            }
+        );
+        let dropped: Vec<Option<DropReason>> =
+            second_run.posts.into_iter().map(|(_, d)| d).collect();
+        assert_eq!(dropped, vec![Some(DropReason::Unchanged), None]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_enriched_posts_keep_their_drop_reasons() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        let uri = mock_server.uri();
+        let link = |name: &str| format!("{uri}/{name}");
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(rss(&[&link("a"), &link("b")])),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(rss(&[
+                &link("a"),
+                &link("c"),
+                &link("d"),
+            ])))
+            .mount(&mock_server)
+            .await;
+        // Only the articles of the new posts are fetched, once.
+        for article in ["/a", "/b", "/c", "/d"] {
+            Mock::given(method("GET"))
+                .and(path(article))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<html><body><article><p>The article's first paragraph, which tells the whole story.</p></article></body></html>",
+                ))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        let mut context = context(Duration::from_secs(5));
+        context.max_posts = 1;
+        context.enricher = Some(Arc::new(
+            ArticleEnricher::new(2, Duration::from_secs(5))
+                .with_missing_fields(false)
+                .with_body(100),
+        ));
+        let source = Source::new("feed", Feed::new(&format!("{uri}/feed"), "Feed"));
+        let (tx, _rx) = mpsc::channel(10);
+
+        // Test
+        scrape_and_send(&context, &tx, &source).await;
+        let second_run = scrape_source(&context, &source).await?.unwrap();
+
+        // Assert
+        let dropped: Vec<(Option<String>, Option<DropReason>)> = second_run
+            .posts
+            .into_iter()
+            .map(|(p, d)| (p.body, d))
+            .collect();
+        let body = Some(String::from(
+            "The article's first paragraph, which tells the whole story.",
+        ));
+        assert_eq!(
+            dropped,
+            vec![
+                (None, Some(DropReason::Unchanged)),
+                (body.clone(), None),
+                (body, Some(DropReason::OverLimit)),
+            ]
+        );
+        mock_server.verify().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_failing_to_publish_during_the_run_is_forgotten() -> Result<(), anyhow::Error>
+    {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(rss(&["https://some-news.ro/a", "https://some-news.ro/b"])),
+            )
+            .mount(&mock_server)
+            .await;
+        let context = context(Duration::from_secs(5));
+        let source = Source::new(
+            "feed",
+            Feed::new(&format!("{}/feed", mock_server.uri()), "Feed"),
+        );
+        // The run waits to send the second post until the first one is published.
+        let (tx, mut rx) = mpsc::channel(1);
+
+        // Test
+        let publish = async {
+            let failed = rx.recv().await.and_then(|p| seen_link(&p));
+            context.seen_links.forget(&failed.unwrap()).await;
+            rx.recv().await
+        };
+        let (_, published) = tokio::join!(scrape_and_send(&context, &tx, &source), publish);
+
+        // Assert
+        assert!(published.is_some());
+        assert_eq!(
+            context.seen_links.previous("feed").await,
+            HashSet::from([String::from("https://some-news.ro/b")])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_posts_not_handed_off_are_offered_again() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<rss version=\"2.0\"><channel>\
+                 <item><link>https://some-news.ro/incomplete</link></item>\
+                 <item><title>Unsent</title><link>https://some-news.ro/unsent</link></item>\
+                 </channel></rss>",
+            ))
+            .mount(&mock_server)
+            .await;
+        let context = context(Duration::from_secs(5));
+        let source = Source::new(
+            "feed",
+            Feed::new(&format!("{}/feed", mock_server.uri()), "Feed"),
+        );
+        // The receiver is gone, so the complete post can't be handed off.
+        let (tx, rx) = mpsc::channel(10);
+        drop(rx);
+
+        // Test
+        scrape_and_send(&context, &tx, &source).await;
+        let second_run = scrape_source(&context, &source).await?.unwrap();
+
+        // Assert
+        assert_eq!(second_run.changes.unchanged, 0);
+        let dropped: Vec<Option<DropReason>> =
+            second_run.posts.into_iter().map(|(_, d)| d).collect();
+        assert_eq!(dropped, vec![Some(DropReason::Incomplete), None]);
+        assert!(context.seen_links.previous("feed").await.is_empty());
+        Ok(())
+    }
+}
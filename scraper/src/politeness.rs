@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The error returned when a fetch is skipped because of the crawling policy.
+#[derive(Debug)]
+pub struct FetchSkipped {
+    pub url: String,
+    pub reason: String,
+}
+
+impl fmt::Display for FetchSkipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "skipped {}: {}", self.url, self.reason)
+    }
+}
+
+impl std::error::Error for FetchSkipped {}
+
+/// An allow or disallow rule from robots.txt.
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+/// Returns true if the robots.txt path pattern matches the path.
+/// Supports the `*` wildcard and the `$` end anchor.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<&str> = pattern.split('*').collect();
+
+    let Some(mut rest) = path.strip_prefix(parts[0]) else {
+        return false;
+    };
+    for (i, part) in parts.iter().enumerate().skip(1) {
+        let is_last = i == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// The robots.txt rules which apply to a user agent.
+/// See: https://www.rfc-editor.org/rfc/rfc9309.html
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsTxt {
+    rules: Vec<Rule>,
+    crawl_delay: Option<Duration>,
+}
+
+impl RobotsTxt {
+    /// Allows everything, used when a site has no robots.txt.
+    pub fn allow_all() -> Self {
+        RobotsTxt::default()
+    }
+
+    /// Parses the robots.txt and keeps the groups which apply to the user agent.
+    /// The `*` groups are used when no group names the user agent.
+    pub fn parse(data: &str, user_agent: &str) -> Self {
+        let user_agent = user_agent.to_lowercase();
+        let mut agent_groups = RobotsTxt::default();
+        let mut wildcard_groups = RobotsTxt::default();
+        let mut found_agent = false;
+
+        // The user agents of the current group, a new group starts on the first
+        // user-agent line after a rule.
+        let mut group_agents: Vec<String> = vec![];
+        let mut in_rules = false;
+
+        for line in data.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            if key == "user-agent" {
+                if in_rules {
+                    group_agents.clear();
+                    in_rules = false;
+                }
+                group_agents.push(value.to_lowercase());
+                continue;
+            }
+
+            in_rules = true;
+            let matches_agent = group_agents
+                .iter()
+                .any(|a| a != "*" && user_agent.starts_with(a.as_str()));
+            let is_wildcard = group_agents.iter().any(|a| a == "*");
+            found_agent |= matches_agent;
+
+            let mut targets: Vec<&mut RobotsTxt> = vec![];
+            if matches_agent {
+                targets.push(&mut agent_groups);
+            }
+            if is_wildcard {
+                targets.push(&mut wildcard_groups);
+            }
+
+            for target in targets {
+                match key.as_str() {
+                    "allow" | "disallow" if !value.is_empty() => target.rules.push(Rule {
+                        allow: key == "allow",
+                        pattern: value.to_string(),
+                    }),
+                    "crawl-delay" => {
+                        if let Ok(seconds) = value.parse::<f64>()
+                            && seconds.is_finite()
+                            && seconds >= 0.0
+                        {
+                            target.crawl_delay = Some(Duration::from_secs_f64(seconds));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if found_agent {
+            agent_groups
+        } else {
+            wildcard_groups
+        }
+    }
+
+    /// Returns true if the path, including the query, may be fetched.
+    /// The most specific matching rule wins, allow wins on ties.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+
+        self.rules
+            .iter()
+            .filter(|r| pattern_matches(&r.pattern, path))
+            .max_by_key(|r| (r.pattern.len(), r.allow))
+            .is_none_or(|r| r.allow)
+    }
+
+    /// Returns the crawl delay requested by the site.
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+}
+
+/// Spaces out the requests made to the same origin, e.g. `https://www.g4media.ro`.
+#[derive(Default)]
+pub struct HostThrottle {
+    /// The earliest time the next request to each origin may start.
+    next_slots: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostThrottle {
+    /// Waits until a request to the origin may be made, then reserves the next slot `delay` later.
+    pub async fn wait(&self, origin: &str, delay: Duration) {
+        let slot = {
+            let mut next_slots = self.next_slots.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_slots
+                .get(origin)
+                .copied()
+                .filter(|s| *s > now)
+                .unwrap_or(now);
+            next_slots.insert(origin.to_string(), slot + delay);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = r#"
+# Some comment
+User-agent: *
+Disallow: /private/
+Allow: /private/public
+Crawl-delay: 2
+
+User-agent: BadBot
+User-agent: OtherBot
+Disallow: /
+
+User-agent: SocialMediaNewsBot
+Disallow: /search
+Disallow: /*.pdf$
+Allow: /search/about
+Crawl-delay: 0.5
+"#;
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("/", "/anything"));
+        assert!(pattern_matches("/private", "/private/page"));
+        assert!(!pattern_matches("/private", "/public"));
+        assert!(pattern_matches("/*.pdf$", "/files/a.pdf"));
+        assert!(!pattern_matches("/*.pdf$", "/files/a.pdf?download=1"));
+        assert!(pattern_matches("/*/edit", "/posts/1/edit"));
+        assert!(pattern_matches("/exact$", "/exact"));
+        assert!(!pattern_matches("/exact$", "/exact/more"));
+    }
+
+    #[test]
+    fn test_robots_txt_agent_group() {
+        let robots = RobotsTxt::parse(ROBOTS, "SocialMediaNewsBot");
+
+        assert!(!robots.is_allowed("/search?q=news"));
+        assert!(robots.is_allowed("/search/about"));
+        assert!(!robots.is_allowed("/files/report.pdf"));
+        assert!(robots.is_allowed("/private/page"));
+        assert_eq!(robots.crawl_delay(), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_robots_txt_wildcard_group() {
+        let robots = RobotsTxt::parse(ROBOTS, "SomeOtherCrawler");
+
+        assert!(!robots.is_allowed("/private/page"));
+        assert!(robots.is_allowed("/private/public/page"));
+        assert!(robots.is_allowed("/search"));
+        assert_eq!(robots.crawl_delay(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_robots_txt_multiple_agents_group() {
+        let robots = RobotsTxt::parse(ROBOTS, "otherbot");
+
+        assert!(!robots.is_allowed("/"));
+        assert!(robots.is_allowed("/robots.txt"));
+        assert_eq!(robots.crawl_delay(), None);
+    }
+
+    #[test]
+    fn test_robots_txt_empty_disallow_allows_all() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow:\n", "SocialMediaNewsBot");
+
+        assert!(robots.is_allowed("/anything"));
+    }
+
+    #[test]
+    fn test_robots_txt_allow_all() {
+        assert!(RobotsTxt::allow_all().is_allowed("/anything"));
+    }
+
+    #[tokio::test]
+    async fn test_host_throttle_spaces_requests() {
+        let throttle = HostThrottle::default();
+        let delay = Duration::from_millis(100);
+        let start = Instant::now();
+
+        throttle.wait("some-news.ro", delay).await;
+        throttle.wait("other-news.ro", delay).await;
+        let first_elapsed = start.elapsed();
+        throttle.wait("some-news.ro", delay).await;
+        throttle.wait("some-news.ro", delay).await;
+        let second_elapsed = start.elapsed();
+
+        assert!(first_elapsed < delay);
+        assert!(second_elapsed >= delay * 2);
+    }
+}
@@ -0,0 +1,245 @@
+//! A readability-style extraction of an article's text.
+//!
+//! The paragraphs of the page are scored by their length and commas, each paragraph adds its score
+//! to its parent and half of it to its grandparent. The best scored container, penalized by its
+//! link density, holds the article, its siblings with a close enough score are kept as well.
+//! Navigation, ads, comments and the like are ignored by their tag, class or id.
+
+use post::{normalize_text, truncate_text};
+use scraper::{ElementRef, Html, Selector};
+use std::collections::{HashMap, HashSet};
+
+/// Elements which never hold the article's text.
+const UNLIKELY_TAGS: [&str; 10] = [
+    "aside", "button", "footer", "form", "header", "iframe", "nav", "noscript", "script", "style",
+];
+
+/// Class or id words of the elements which are unlikely to hold the article's text.
+const UNLIKELY_WORDS: [&str; 27] = [
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "cookie",
+    "cookies",
+    "disqus",
+    "footer",
+    "header",
+    "menu",
+    "nav",
+    "navbar",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "social",
+    "sponsored",
+    "subscribe",
+    "widget",
+];
+
+/// Class or id words of the elements which likely hold the article's text.
+const LIKELY_WORDS: [&str; 8] = [
+    "article", "body", "content", "entry", "main", "post", "story", "text",
+];
+
+/// Paragraphs shorter than this number of characters are not scored.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// The siblings of the best container scored at least this fraction of its score are kept.
+const SIBLING_SCORE_RATIO: f64 = 0.5;
+
+/// Returns the lowercase words of the element's class and id.
+fn class_words(element: ElementRef) -> Vec<String> {
+    [element.attr("class"), element.attr("id")]
+        .into_iter()
+        .flatten()
+        .flat_map(|value| value.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Returns true if the element doesn't hold the article's text, judging by its tag, class and id.
+/// Elements which are also likely to hold it, e.g. `article-header`, are kept.
+fn is_unlikely(element: ElementRef) -> bool {
+    if UNLIKELY_TAGS.contains(&element.value().name()) {
+        return true;
+    }
+    let words = class_words(element);
+    let has = |list: &[&str]| words.iter().any(|w| list.contains(&w.as_str()));
+    has(&UNLIKELY_WORDS) && !has(&LIKELY_WORDS)
+}
+
+/// Returns true if the element or one of its ancestors doesn't hold the article's text.
+fn is_in_unlikely(element: ElementRef) -> bool {
+    is_unlikely(element)
+        || element
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(is_unlikely)
+}
+
+/// Scores the element by its class and id.
+fn class_weight(element: ElementRef) -> f64 {
+    let words = class_words(element);
+    let has = |list: &[&str]| words.iter().any(|w| list.contains(&w.as_str()));
+    let mut weight = 0.0;
+    if has(&LIKELY_WORDS) {
+        weight += 25.0;
+    }
+    if has(&UNLIKELY_WORDS) {
+        weight -= 25.0;
+    }
+    weight
+}
+
+/// Returns the fraction of the element's text which is inside links.
+fn link_density(element: ElementRef) -> f64 {
+    let length = element.text().map(|t| t.trim().len()).sum::<usize>();
+    if length == 0 {
+        return 0.0;
+    }
+    let selector = Selector::parse("a").expect("valid selector");
+    let link_length = element
+        .select(&selector)
+        .flat_map(|a| a.text())
+        .map(|t| t.trim().len())
+        .sum::<usize>();
+    link_length as f64 / length as f64
+}
+
+/// Extracts the plain text of the article from its page, the paragraphs are separated by blank lines.
+/// The text is cut to at most `max_chars` characters, returns `None` if no article text was found.
+pub fn extract_article_text(html: &str, max_chars: usize) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("p, pre").expect("valid selector");
+
+    let paragraphs: Vec<(ElementRef, String)> = document
+        .select(&selector)
+        .filter(|p| !is_in_unlikely(*p))
+        .map(|p| (p, normalize_text(&p.text().collect::<String>())))
+        .filter(|(_, text)| !text.is_empty())
+        .collect();
+
+    let mut candidates = HashMap::new();
+    for (paragraph, text) in &paragraphs {
+        let length = text.chars().count();
+        if length < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (length / 100).min(3) as f64;
+        let ancestors = paragraph.ancestors().filter_map(ElementRef::wrap).take(2);
+        for (level, ancestor) in ancestors.enumerate() {
+            let (_, candidate_score) = candidates
+                .entry(ancestor.id())
+                .or_insert_with(|| (ancestor, class_weight(ancestor)));
+            *candidate_score += score / (level + 1) as f64;
+        }
+    }
+
+    let scores: HashMap<_, _> = candidates
+        .into_iter()
+        .map(|(id, (element, score))| (id, (element, score * (1.0 - link_density(element)))))
+        .collect();
+    let (top, top_score) = scores
+        .values()
+        .copied()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    // The article may be split in several sibling containers.
+    let mut containers = HashSet::from([top.id()]);
+    if let Some(parent) = top.parent() {
+        containers.extend(
+            parent
+                .children()
+                .filter_map(|sibling| scores.get(&sibling.id()))
+                .filter(|(_, score)| *score >= top_score * SIBLING_SCORE_RATIO)
+                .map(|(sibling, _)| sibling.id()),
+        );
+    }
+
+    let text = paragraphs
+        .iter()
+        .filter(|(paragraph, _)| paragraph.ancestors().any(|a| containers.contains(&a.id())))
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    if text.is_empty() {
+        None
+    } else {
+        Some(truncate_text(&text, max_chars))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = r#"
+<html>
+<head><title>Some news</title><script>var ads = "Loading, please wait, this is not the article";</script></head>
+<body>
+  <header><p>Some News, the news you can trust, every day, all day long.</p></header>
+  <nav><ul><li><a href="/">Home</a></li><li><a href="/politics">Politics</a></li></ul></nav>
+  <div id="main">
+    <div class="article-body">
+      <h1>The title</h1>
+      <p>The first paragraph of the article, long enough to be scored, with a comma.</p>
+      <div class="ad-slot"><p>Buy our product, it's great, it's cheap, it's everything you need.</p></div>
+      <p>The second paragraph of the <a href="/more">article</a> continues the story &amp; ends it.</p>
+      <p>Short.</p>
+    </div>
+    <div class="comments">
+      <p>A comment on the article, which is long enough, and has commas, too many of them.</p>
+    </div>
+    <aside><p>The related articles, which should not be part of the text, at all.</p></aside>
+  </div>
+  <footer><p>Copyright Some News, all rights reserved, since the beginning of time.</p></footer>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_extract_article_text() {
+        assert_eq!(
+            extract_article_text(ARTICLE, 1000),
+            Some(String::from(
+                "The first paragraph of the article, long enough to be scored, with a comma.\n\n\
+                 The second paragraph of the article continues the story & ends it.\n\n\
+                 Short."
+            ))
+        );
+    }
+
+    #[test]
+    fn test_extract_article_text_siblings() {
+        let html = r#"<html><body><div>
+          <div class="part"><p>The first part of the article, which is long, very long, longer than that.</p></div>
+          <div class="part"><p>The second part of the article, which is long, very long, just as long.</p></div>
+          <div class="links"><p><a href="/a">A link which is long enough to be a paragraph, really.</a></p></div>
+        </div></body></html>"#;
+
+        assert_eq!(
+            extract_article_text(html, 1000),
+            Some(String::from(
+                "The first part of the article, which is long, very long, longer than that.\n\n\
+                 The second part of the article, which is long, very long, just as long."
+            ))
+        );
+    }
+
+    #[test]
+    fn test_extract_article_text_without_paragraphs() {
+        let html =
+            "<html><body><nav><p>Home, politics, sports, weather and more.</p></nav></body></html>";
+
+        assert_eq!(extract_article_text(html, 1000), None);
+    }
+}
@@ -0,0 +1,290 @@
+use anyhow::{Context, anyhow};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The schedule of a source as written in the configuration file.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum ScheduleConfig {
+    /// Runs every `interval_minutes` minutes.
+    Interval { interval_minutes: u32 },
+    /// Runs according to the cron expression, evaluated in the timezone. Defaults to UTC.
+    Cron {
+        cron: String,
+        timezone: Option<String>,
+    },
+}
+
+/// The names of the days of the week, starting with Sunday.
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Parses a day of the week numbered from 0 to 7, both 0 and 7 are Sunday, or named, e.g. `MON`.
+fn parse_day_of_week(day: &str) -> Result<u32, anyhow::Error> {
+    let named = DAY_NAMES
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(day))
+        .map(|position| position as u32);
+    named
+        .or_else(|| day.parse::<u32>().ok().filter(|day| *day <= 7))
+        .ok_or_else(|| anyhow!("invalid day of the week `{day}`"))
+}
+
+/// Translates the day of the week field of a standard cron expression, where Sunday is 0 or 7,
+/// to the numbering of the `cron` crate, where Sunday is 1 and Saturday is 7.
+/// The day names are translated along with the numbers, so fields mixing both, e.g. `MON-5`, work too.
+/// Fields starting with `*` or `?` are the same in both and are kept.
+fn translate_day_of_week(field: &str) -> Result<String, anyhow::Error> {
+    if field.starts_with(['*', '?']) {
+        return Ok(field.to_string());
+    }
+
+    let mut days = [false; 7];
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (item, None),
+        };
+        let (start, end) = match (range.split_once('-'), step) {
+            // A range may end on Sunday, e.g. `SAT-SUN`.
+            (Some((start, end)), _) => match (parse_day_of_week(start)?, parse_day_of_week(end)?) {
+                (start, 0) if start > 0 => (start, 7),
+                (start, end) => (start, end),
+            },
+            (None, Some(_)) => (parse_day_of_week(range)?, 6),
+            (None, None) => (parse_day_of_week(range)?, parse_day_of_week(range)?),
+        };
+        let step = match step {
+            Some(step) => step
+                .parse::<usize>()
+                .ok()
+                .filter(|step| *step > 0)
+                .ok_or_else(|| anyhow!("invalid step `{step}`"))?,
+            None => 1,
+        };
+        if start > end {
+            return Err(anyhow!("invalid days of the week `{item}`"));
+        }
+        for day in (start..=end).step_by(step) {
+            days[(day % 7) as usize] = true;
+        }
+    }
+    Ok(days
+        .iter()
+        .enumerate()
+        .filter(|(_, selected)| **selected)
+        .map(|(day, _)| (day + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Decides when a source is scraped.
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    Interval(Duration),
+    Cron {
+        schedule: Box<cron::Schedule>,
+        timezone: Tz,
+    },
+}
+
+impl Schedule {
+    /// Creates a schedule running at a fixed interval.
+    pub fn every(interval: Duration) -> Self {
+        Schedule::Interval(interval)
+    }
+
+    /// Creates a schedule from a cron expression evaluated in the timezone.
+    /// Accepts the standard 5 fields expressions, where Sunday is 0 or 7, or the 6-7 fields ones
+    /// which include the seconds and follow the `cron` crate, where Sunday is 1 and Saturday is 7.
+    pub fn cron(expression: &str, timezone: Tz) -> Result<Self, anyhow::Error> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let expression = if let [minute, hour, day, month, day_of_week] = fields[..] {
+            let day_of_week = translate_day_of_week(day_of_week)
+                .with_context(|| format!("invalid cron expression `{expression}`"))?;
+            format!("0 {minute} {hour} {day} {month} {day_of_week}")
+        } else {
+            expression.trim().to_string()
+        };
+        let schedule = cron::Schedule::from_str(&expression)
+            .with_context(|| format!("invalid cron expression `{expression}`"))?;
+        Ok(Schedule::Cron {
+            schedule: Box::new(schedule),
+            timezone,
+        })
+    }
+
+    /// Returns the next time the source should be scraped after the given time.
+    pub fn next_run(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Interval(interval) => {
+                Some(after + chrono::Duration::from_std(*interval).ok()?)
+            }
+            Schedule::Cron { schedule, timezone } => schedule
+                .after(&after.with_timezone(timezone))
+                .next()
+                .map(|next| next.with_timezone(&Utc)),
+        }
+    }
+}
+
+impl TryFrom<&ScheduleConfig> for Schedule {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &ScheduleConfig) -> Result<Self, Self::Error> {
+        match value {
+            ScheduleConfig::Interval { interval_minutes } => {
+                if *interval_minutes == 0 {
+                    return Err(anyhow!("the interval must be at least one minute"));
+                }
+                Ok(Schedule::every(Duration::from_secs(
+                    *interval_minutes as u64 * 60,
+                )))
+            }
+            ScheduleConfig::Cron { cron, timezone } => {
+                let timezone = match timezone {
+                    Some(timezone) => Tz::from_str(timezone)
+                        .map_err(|e| anyhow!("invalid timezone `{timezone}`: {e}"))?,
+                    None => Tz::UTC,
+                };
+                Schedule::cron(cron, timezone)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_interval_next_run() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+        let schedule = Schedule::every(Duration::from_secs(5 * 60));
+
+        assert_eq!(
+            schedule.next_run(now),
+            Some(Utc.with_ymd_and_hms(2025, 1, 1, 10, 5, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_cron_next_run_in_timezone() -> Result<(), anyhow::Error> {
+        // Every 15 minutes between 06:00 and 23:59, Bucharest time (UTC+2 in winter).
+        let schedule = Schedule::cron("*/15 6-23 * * *", chrono_tz::Europe::Bucharest)?;
+
+        let night = Utc.with_ymd_and_hms(2025, 1, 1, 1, 0, 0).unwrap();
+        let day = Utc.with_ymd_and_hms(2025, 1, 1, 10, 7, 0).unwrap();
+
+        assert_eq!(
+            schedule.next_run(night),
+            Some(Utc.with_ymd_and_hms(2025, 1, 1, 4, 0, 0).unwrap())
+        );
+        assert_eq!(
+            schedule.next_run(day),
+            Some(Utc.with_ymd_and_hms(2025, 1, 1, 10, 15, 0).unwrap())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cron_day_of_week() -> Result<(), anyhow::Error> {
+        // 2025-01-03 is a Friday.
+        let friday = Utc.with_ymd_and_hms(2025, 1, 3, 10, 0, 0).unwrap();
+        let weekdays = Schedule::cron("0 8 * * 1-5", Tz::UTC)?;
+        let sundays = Schedule::cron("0 8 * * 0", Tz::UTC)?;
+
+        assert_eq!(
+            weekdays.next_run(friday),
+            Some(Utc.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap())
+        );
+        assert_eq!(
+            sundays.next_run(friday),
+            Some(Utc.with_ymd_and_hms(2025, 1, 5, 8, 0, 0).unwrap())
+        );
+        assert_eq!(
+            Schedule::cron("0 8 * * 7", Tz::UTC)?.next_run(friday),
+            sundays.next_run(friday)
+        );
+        assert_eq!(
+            Schedule::cron("0 8 * * MON-FRI", Tz::UTC)?.next_run(friday),
+            weekdays.next_run(friday)
+        );
+        assert_eq!(
+            Schedule::cron("0 8 * * MON-5", Tz::UTC)?.next_run(friday),
+            weekdays.next_run(friday)
+        );
+        assert_eq!(
+            Schedule::cron("0 8 * * 0,SAT", Tz::UTC)?.next_run(friday),
+            Some(Utc.with_ymd_and_hms(2025, 1, 4, 8, 0, 0).unwrap())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_translate_day_of_week() {
+        assert_eq!(translate_day_of_week("1-5").unwrap(), "2,3,4,5,6");
+        assert_eq!(translate_day_of_week("0").unwrap(), "1");
+        assert_eq!(translate_day_of_week("5-7").unwrap(), "1,6,7");
+        assert_eq!(translate_day_of_week("0,6").unwrap(), "1,7");
+        assert_eq!(translate_day_of_week("1/2").unwrap(), "2,4,6");
+        assert_eq!(translate_day_of_week("*").unwrap(), "*");
+        assert_eq!(translate_day_of_week("SAT,SUN").unwrap(), "1,7");
+        assert_eq!(translate_day_of_week("mon-fri").unwrap(), "2,3,4,5,6");
+        assert_eq!(translate_day_of_week("SAT-SUN").unwrap(), "1,7");
+        assert_eq!(translate_day_of_week("MON-5").unwrap(), "2,3,4,5,6");
+        assert_eq!(translate_day_of_week("1,SAT").unwrap(), "2,7");
+        assert!(translate_day_of_week("MONDAY").is_err());
+        assert!(translate_day_of_week("8").is_err());
+        assert!(translate_day_of_week("5-1").is_err());
+        assert!(translate_day_of_week("1-5/0").is_err());
+    }
+
+    #[test]
+    fn test_cron_with_seconds() -> Result<(), anyhow::Error> {
+        let schedule = Schedule::cron("30 0 * * * *", Tz::UTC)?;
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+
+        assert_eq!(
+            schedule.next_run(now),
+            Some(Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 30).unwrap())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule_from_config() {
+        let interval = ScheduleConfig::Interval {
+            interval_minutes: 5,
+        };
+        let cron = ScheduleConfig::Cron {
+            cron: String::from("0 * * * *"),
+            timezone: Some(String::from("Europe/Bucharest")),
+        };
+
+        assert!(Schedule::try_from(&interval).is_ok());
+        assert!(Schedule::try_from(&cron).is_ok());
+    }
+
+    #[test]
+    fn test_schedule_from_invalid_config() {
+        let zero_interval = ScheduleConfig::Interval {
+            interval_minutes: 0,
+        };
+        let invalid_cron = ScheduleConfig::Cron {
+            cron: String::from("every five minutes"),
+            timezone: None,
+        };
+        let invalid_timezone = ScheduleConfig::Cron {
+            cron: String::from("0 * * * *"),
+            timezone: Some(String::from("Mars/Olympus_Mons")),
+        };
+
+        assert!(Schedule::try_from(&zero_interval).is_err());
+        assert!(Schedule::try_from(&invalid_cron).is_err());
+        assert!(Schedule::try_from(&invalid_timezone).is_err());
+    }
+}
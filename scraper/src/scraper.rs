@@ -1,48 +1,549 @@
+use crate::politeness::{FetchSkipped, HostThrottle, RobotsTxt};
+use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use infrastructure::RedisService;
+use log::{debug, warn};
 use post::NewsPost;
+use reqwest::StatusCode;
+use reqwest::header::{
+    CONTENT_TYPE, ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
 use reqwest_middleware::ClientBuilder;
-use reqwest_retry::RetryTransientMiddleware;
 use reqwest_retry::policies::ExponentialBackoff;
+use reqwest_retry::{
+    DefaultRetryableStrategy, RetryTransientMiddleware, Retryable, RetryableStrategy,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+use url::Url;
 
 /// Represents a web scraper which is can be scraped by the engine.
 #[async_trait]
 pub(crate) trait ScrapableWebPage: Send + Sync {
     fn get_url(&self) -> String;
     fn get_posts(&self, html: String) -> Result<Vec<NewsPost>, anyhow::Error>;
+
+    /// The URLs of all the pages scraped with this target, defaults to its URL.
+    fn get_urls(&self) -> Vec<String> {
+        vec![self.get_url()]
+    }
+
+    /// Returns the URL of the page following the page at the given URL, possibly relative.
+    /// Returns `None` if it's the last page or if the target doesn't support pagination.
+    fn get_next_page_url(&self, _url: &str, _html: &str) -> Option<String> {
+        None
+    }
+
+    /// How many next pages are followed from each of the target's URLs.
+    fn get_max_depth(&self) -> usize {
+        0
+    }
 }
 
+/// Resolves a possibly relative URL against the base URL.
+/// Returns `None` if the URL is invalid or if it's not an http(s) URL.
+pub(crate) fn resolve_url(base: &str, value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let resolved = match Url::parse(base) {
+        Ok(base) => base.join(value),
+        Err(_) => Url::parse(value),
+    }
+    .ok()?;
+
+    match resolved.scheme() {
+        "http" | "https" => Some(resolved.to_string()),
+        _ => None,
+    }
+}
+
+/// Parses a publication time from a `<time datetime>` attribute or metadata.
+/// Accepts RFC 3339, RFC 2822 and ISO 8601 dates without a timezone, which are in the given timezone.
+pub(crate) fn parse_timestamp(value: &str, timezone: Tz) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    if let Ok(timestamp) = DateTime::parse_from_rfc2822(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .and_then(|timestamp| timezone.from_local_datetime(&timestamp).earliest())
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// Resolves the post's link and image against the base URL, dropping the invalid ones.
+pub(crate) fn resolve_post_urls(base: &str, post: &mut NewsPost) {
+    post.link = post.link.as_deref().and_then(|l| resolve_url(base, l));
+    post.image = post.image.as_deref().and_then(|i| resolve_url(base, i));
+}
+
+/// Cleans up a post returned by a target scraped from the base URL.
+pub(crate) fn clean_post(base: &str, post: &mut NewsPost) {
+    resolve_post_urls(base, post);
+    post.normalize();
+}
+
+/// The validators used to make conditional requests for a page.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// Reads the validators from the response headers.
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        CacheValidators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+
+    /// Returns true if there are no validators.
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// The time in seconds the validators are kept in Redis.
+const CACHE_VALIDATORS_TTL: u64 = 60 * 60 * 24 * 7;
+
+/// The user agent sent with every request.
+const USER_AGENT: &str = "SocialMediaNewsBot/1.0";
+
+/// The user agent matched against the robots.txt groups.
+const ROBOTS_USER_AGENT: &str = "SocialMediaNewsBot";
+
+/// How long a robots.txt is cached.
+const ROBOTS_TXT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How long the failure to fetch a robots.txt is cached, the site's pages fail to be fetched meanwhile.
+const ROBOTS_TXT_ERROR_TTL: Duration = Duration::from_secs(60 * 5);
+
+/// The start of a resource, fetched without downloading all of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceHead {
+    /// The MIME type from the Content-Type header, without its parameters.
+    pub content_type: Option<String>,
+    /// The size in bytes from the Content-Length header. Without it, the number of bytes read,
+    /// which stops past the maximum size.
+    pub size: u64,
+    /// The first bytes of the resource.
+    pub bytes: Vec<u8>,
+}
+
+/// The cached robots.txt of an origin, or the error fetching it, along with its expiry time,
+/// `None` until it's fetched.
+type RobotsTxtEntry = tokio::sync::Mutex<Option<(Instant, Result<Arc<RobotsTxt>, String>)>>;
+
 /// The web scraper engine is used to scrape web pages.
 pub struct WebScrapperEngine {
     client: reqwest_middleware::ClientWithMiddleware,
+    /// The cache validators of each page, keyed by URL.
+    cache_validators: Mutex<HashMap<String, CacheValidators>>,
+    /// Optionally persists the cache validators across restarts.
+    cache_store: Option<RedisService>,
+    /// Whether the robots.txt of each origin is respected.
+    respect_robots_txt: bool,
+    /// The robots.txt of each origin along with its expiry time, each origin is locked on its own
+    /// so a slow robots.txt doesn't hold back the requests to the other origins.
+    robots_txt: Mutex<HashMap<String, Arc<RobotsTxtEntry>>>,
+    /// The minimum delay between two requests to the same origin.
+    min_host_delay: Duration,
+    host_throttle: HostThrottle,
+}
+
+/// Retries the transient failures like the default strategy, except for the timeouts:
+/// a host which didn't answer in time isn't waited for again.
+struct RetryUnlessTimedOut;
+
+impl RetryableStrategy for RetryUnlessTimedOut {
+    fn handle(
+        &self,
+        res: &Result<reqwest::Response, reqwest_middleware::Error>,
+    ) -> Option<Retryable> {
+        match res {
+            Err(reqwest_middleware::Error::Reqwest(err)) if err.is_timeout() => {
+                Some(Retryable::Fatal)
+            }
+            _ => DefaultRetryableStrategy.handle(res),
+        }
+    }
+}
+
+/// Builds the HTTP client with retries, the timeouts apply to each attempt and aren't retried.
+fn build_client(
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+) -> reqwest_middleware::ClientWithMiddleware {
+    let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+    let mut http_client = reqwest::Client::builder().user_agent(USER_AGENT);
+    if let Some(connect_timeout) = connect_timeout {
+        http_client = http_client.connect_timeout(connect_timeout);
+    }
+    if let Some(read_timeout) = read_timeout {
+        http_client = http_client.read_timeout(read_timeout);
+    }
+    let http_client = http_client.build().expect("Failed to build HTTP client");
+    ClientBuilder::new(http_client)
+        .with(RetryTransientMiddleware::new_with_policy_and_strategy(
+            retry_policy,
+            RetryUnlessTimedOut,
+        ))
+        .build()
 }
 
 impl Default for WebScrapperEngine {
     fn default() -> Self {
-        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
-        let client = ClientBuilder::new(reqwest::Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
-            .build();
-
-        WebScrapperEngine { client }
+        WebScrapperEngine {
+            client: build_client(None, None),
+            cache_validators: Mutex::new(HashMap::new()),
+            cache_store: None,
+            respect_robots_txt: true,
+            robots_txt: Mutex::new(HashMap::new()),
+            min_host_delay: Duration::ZERO,
+            host_throttle: HostThrottle::default(),
+        }
     }
 }
 
 impl WebScrapperEngine {
-    pub async fn get_posts<P>(&self, web_page: P) -> Result<Vec<NewsPost>, anyhow::Error>
-    where
-        P: ScrapableWebPage,
-    {
-        let body = self
+    /// Persists the cache validators in Redis in addition to memory.
+    pub fn with_cache_store(mut self, redis_service: RedisService) -> Self {
+        self.cache_store = Some(redis_service);
+        self
+    }
+
+    /// Sets the timeout for connecting to a host and the timeout between two reads of a response.
+    pub fn with_timeouts(mut self, connect_timeout: Duration, read_timeout: Duration) -> Self {
+        self.client = build_client(Some(connect_timeout), Some(read_timeout));
+        self
+    }
+
+    /// Sets whether the robots.txt of each origin is respected.
+    pub fn with_robots_txt(mut self, respect_robots_txt: bool) -> Self {
+        self.respect_robots_txt = respect_robots_txt;
+        self
+    }
+
+    /// Sets the minimum delay between two requests to the same origin.
+    pub fn with_min_host_delay(mut self, min_host_delay: Duration) -> Self {
+        self.min_host_delay = min_host_delay;
+        self
+    }
+
+    /// Fetches the robots.txt of the origin.
+    /// A missing robots.txt allows everything, an unreachable one is an error.
+    async fn fetch_robots_txt(&self, origin: &str) -> Result<RobotsTxt, anyhow::Error> {
+        let robots_url = format!("{origin}/robots.txt");
+        let response = self
             .client
-            .get(web_page.get_url())
+            .get(&robots_url)
             .send()
-            .await?
-            .error_for_status()?
-            .text()
-            .await?;
+            .await
+            .map_err(|err| anyhow!("Failed to fetch {robots_url}: {err}"))?;
+
+        let status = response.status();
+        if status.is_success() {
+            let data = response
+                .text()
+                .await
+                .map_err(|err| anyhow!("Failed to read {robots_url}: {err}"))?;
+            Ok(RobotsTxt::parse(&data, ROBOTS_USER_AGENT))
+        } else if status.is_client_error() {
+            Ok(RobotsTxt::allow_all())
+        } else {
+            Err(anyhow!("Failed to fetch {robots_url}, got {status}"))
+        }
+    }
+
+    /// Returns the cached robots.txt of the origin, fetching it when needed.
+    /// Returns an error if the robots.txt is unreachable, the error is cached for a while.
+    async fn get_robots_txt(&self, origin: &str) -> Result<Arc<RobotsTxt>, anyhow::Error> {
+        let entry = self
+            .robots_txt
+            .lock()
+            .unwrap()
+            .entry(origin.to_string())
+            .or_default()
+            .clone();
+        // Concurrent requests to the origin wait for a single fetch.
+        let mut entry = entry.lock().await;
+        if let Some((expires_at, robots)) = entry.as_ref()
+            && *expires_at > Instant::now()
+        {
+            return robots.clone().map_err(|err| anyhow!(err));
+        }
+
+        let (ttl, robots) = match self.fetch_robots_txt(origin).await {
+            Ok(robots) => (ROBOTS_TXT_TTL, Ok(Arc::new(robots))),
+            Err(err) => {
+                warn!("{err}");
+                (ROBOTS_TXT_ERROR_TTL, Err(err.to_string()))
+            }
+        };
+        *entry = Some((Instant::now() + ttl, robots.clone()));
+        robots.map_err(|err| anyhow!(err))
+    }
+
+    /// Applies the crawling policy before fetching the URL: checks the robots.txt
+    /// and waits until the host may be requested again.
+    /// Returns a [`FetchSkipped`] error if the URL is disallowed and an error if the robots.txt is unreachable.
+    async fn wait_for_policy(&self, url: &str) -> Result<(), anyhow::Error> {
+        let parsed_url = Url::parse(url)?;
+        let origin = parsed_url.origin().ascii_serialization();
+        let mut delay = self.min_host_delay;
+
+        if self.respect_robots_txt {
+            let robots = self.get_robots_txt(&origin).await?;
+            let path = match parsed_url.query() {
+                Some(query) => format!("{}?{}", parsed_url.path(), query),
+                None => parsed_url.path().to_string(),
+            };
+            if !robots.is_allowed(&path) {
+                return Err(FetchSkipped {
+                    url: url.to_string(),
+                    reason: String::from("disallowed by robots.txt"),
+                }
+                .into());
+            }
+            if let Some(crawl_delay) = robots.crawl_delay() {
+                delay = delay.max(crawl_delay);
+            }
+        }
+
+        self.host_throttle.wait(&origin, delay).await;
+        Ok(())
+    }
+
+    /// Returns the Redis key holding the cache validators of the URL.
+    fn cache_key(url: &str) -> String {
+        format!("scraper:http-cache:{url}")
+    }
+
+    /// Returns the known cache validators for the URL.
+    async fn load_cache_validators(&self, url: &str) -> Option<CacheValidators> {
+        let cached = self.cache_validators.lock().unwrap().get(url).cloned();
+        if cached.is_some() {
+            return cached;
+        }
+
+        let mut store = self.cache_store.clone()?;
+        match store
+            .get_value::<CacheValidators>(&Self::cache_key(url))
+            .await
+        {
+            Ok(validators) => validators,
+            Err(err) => {
+                warn!("Failed to load cache validators for {url}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Remembers the cache validators for the URL.
+    async fn save_cache_validators(&self, url: &str, validators: CacheValidators) {
+        if validators.is_empty() {
+            return;
+        }
+        self.cache_validators
+            .lock()
+            .unwrap()
+            .insert(url.to_string(), validators.clone());
+
+        if let Some(mut store) = self.cache_store.clone()
+            && let Err(err) = store
+                .set_value(
+                    &Self::cache_key(url),
+                    &validators,
+                    Some(CACHE_VALIDATORS_TTL),
+                )
+                .await
+        {
+            warn!("Failed to save cache validators for {url}: {err}");
+        }
+    }
+
+    /// Fetches the page at the given URL, with a conditional request if `conditional` is set.
+    /// Returns `None` if the page wasn't modified since it was last fetched.
+    async fn get_page_if_modified(
+        &self,
+        url: &str,
+        conditional: bool,
+    ) -> Result<Option<(String, CacheValidators)>, anyhow::Error> {
+        self.wait_for_policy(url).await?;
+
+        let mut request = self.client.get(url);
+        if conditional && let Some(validators) = self.load_cache_validators(url).await {
+            if let Some(etag) = validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
 
-        let results = web_page.get_posts(body)?;
-        Ok(results)
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+        let validators = CacheValidators::from_headers(response.headers());
+        Ok(Some((response.text().await?, validators)))
+    }
+
+    /// Fetches the page at the given URL and returns its body.
+    /// The timeout applies to the request, not to the wait imposed by the crawling policy.
+    pub async fn get_page(&self, url: &str, timeout: Duration) -> Result<String, anyhow::Error> {
+        self.wait_for_policy(url).await?;
+
+        let request = async {
+            let body = self
+                .client
+                .get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+            Ok::<String, anyhow::Error>(body)
+        };
+        tokio::time::timeout(timeout, request)
+            .await
+            .map_err(|_| anyhow!("timed out fetching {url}"))?
+    }
+
+    /// Fetches the first `head_bytes` bytes of the resource at the given URL.
+    /// When the response has no Content-Length, reads at most one byte past `max_bytes` to find its size.
+    /// The timeout applies to the request, not to the wait imposed by the crawling policy.
+    pub async fn get_head(
+        &self,
+        url: &str,
+        head_bytes: usize,
+        max_bytes: u64,
+        timeout: Duration,
+    ) -> Result<ResourceHead, anyhow::Error> {
+        self.wait_for_policy(url).await?;
+
+        let request = async {
+            let mut response = self.client.get(url).send().await?.error_for_status()?;
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(';').next())
+                .map(|value| value.trim().to_ascii_lowercase());
+            let content_length = response.content_length();
+
+            let mut bytes = Vec::new();
+            let mut read = 0;
+            while bytes.len() < head_bytes || (content_length.is_none() && read <= max_bytes) {
+                let Some(chunk) = response.chunk().await? else {
+                    break;
+                };
+                read += chunk.len() as u64;
+                let take = head_bytes.saturating_sub(bytes.len()).min(chunk.len());
+                bytes.extend_from_slice(&chunk[..take]);
+            }
+            Ok::<ResourceHead, anyhow::Error>(ResourceHead {
+                content_type,
+                size: content_length.unwrap_or(read),
+                bytes,
+            })
+        };
+        tokio::time::timeout(timeout, request)
+            .await
+            .map_err(|_| anyhow!("timed out fetching {url}"))?
+    }
+
+    /// Scrapes the posts from all the pages of the web page, following the next pages up to its maximum depth.
+    /// The posts are merged in the order of the pages, the posts with an already seen link are dropped.
+    /// Returns `None` if the page wasn't modified since it was last scraped. Only single page sources
+    /// are requested conditionally: an unmodified page of a source with several pages would lose its posts.
+    pub async fn get_posts<P>(&self, web_page: &P) -> Result<Option<Vec<NewsPost>>, anyhow::Error>
+    where
+        P: ScrapableWebPage + ?Sized,
+    {
+        let mut results: Vec<NewsPost> = vec![];
+        let mut modified = false;
+        let mut visited = HashSet::new();
+        let mut seen_links = HashSet::new();
+        let mut all_validators = vec![];
+        let urls = web_page.get_urls();
+        let conditional = urls.len() == 1 && web_page.get_max_depth() == 0;
+
+        for url in urls {
+            let mut next_url = Some(url);
+            let mut depth = 0;
+            while let Some(url) = next_url.take() {
+                if !visited.insert(url.clone()) {
+                    break;
+                }
+                let page = match self.get_page_if_modified(&url, conditional).await {
+                    Ok(page) => page,
+                    // The first page decides whether the source failed, a missing next page only ends it.
+                    Err(e) if depth > 0 => {
+                        warn!("Failed to get the next page {url}: {e}");
+                        break;
+                    }
+                    Err(e) => return Err(e),
+                };
+                let Some((body, validators)) = page else {
+                    debug!("Page {url} was not modified since the last scrape");
+                    break;
+                };
+                modified = true;
+
+                if depth < web_page.get_max_depth() {
+                    next_url = web_page
+                        .get_next_page_url(&url, &body)
+                        .and_then(|next| resolve_url(&url, &next));
+                }
+                for mut post in web_page.get_posts(body)? {
+                    clean_post(&url, &mut post);
+                    if let Some(link) = &post.link
+                        && !seen_links.insert(link.clone())
+                    {
+                        continue;
+                    }
+                    results.push(post);
+                }
+                all_validators.push((url, validators));
+                depth += 1;
+            }
+        }
+
+        if !modified {
+            return Ok(None);
+        }
+        // Only remember the pages once all were parsed, so a failed parse is retried.
+        if conditional {
+            for (url, validators) in all_validators {
+                self.save_cache_validators(&url, validators).await;
+            }
+        }
+        Ok(Some(results))
     }
 }
 
@@ -50,16 +551,21 @@ impl WebScrapperEngine {
 mod tests {
     use super::*;
     use anyhow::Error;
-    use wiremock::matchers::{method, path};
+    use chrono::TimeZone;
+    use wiremock::matchers::{header, header_exists, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     struct TestScrapper<'a> {
         mock_server: &'a MockServer,
+        posts: Vec<NewsPost>,
     }
 
     impl<'a> TestScrapper<'a> {
         fn new(mock_server: &'a MockServer) -> Self {
-            TestScrapper { mock_server }
+            TestScrapper {
+                mock_server,
+                posts: Vec::new(),
+            }
         }
     }
 
@@ -69,7 +575,7 @@ mod tests {
         }
 
         fn get_posts(&self, _html: String) -> Result<Vec<NewsPost>, Error> {
-            Ok(Vec::new())
+            Ok(self.posts.clone())
         }
     }
 
@@ -88,9 +594,36 @@ mod tests {
         let test_scraper = TestScrapper::new(&mock_server);
         let default_engine = WebScrapperEngine::default();
         // Test
-        let _ = default_engine.get_posts(test_scraper).await;
+        let _ = default_engine.get_posts(&test_scraper).await;
+
+        // Assert
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_read_timeout() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let test_scraper = TestScrapper::new(&mock_server);
+        let engine = WebScrapperEngine::default()
+            .with_robots_txt(false)
+            .with_timeouts(Duration::from_secs(1), Duration::from_millis(100));
+        let start = Instant::now();
+
+        // Test
+        let result = engine.get_posts(&test_scraper).await;
 
         // Assert
+        assert!(result.is_err());
+        // The timeout isn't retried, so the request is abandoned after the read timeout.
+        assert!(start.elapsed() < Duration::from_secs(1));
         mock_server.verify().await;
     }
 
@@ -109,9 +642,498 @@ mod tests {
         let test_scraper = TestScrapper::new(&mock_server);
         let default_engine = WebScrapperEngine::default();
         // Test
-        let _ = default_engine.get_posts(test_scraper).await;
+        let _ = default_engine.get_posts(&test_scraper).await;
+
+        // Assert
+        mock_server.verify().await;
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let expected = Utc.with_ymd_and_hms(2026, 10, 17, 9, 10, 0).unwrap();
+
+        assert_eq!(
+            parse_timestamp("2026-10-17T12:10:00+03:00", Tz::UTC),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_timestamp("2026-10-17T09:10:00Z", Tz::UTC),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_timestamp("Sat, 17 Oct 2026 09:10:00 +0000", Tz::UTC),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_timestamp(" 2026-10-17T09:10:00 ", Tz::UTC),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_timestamp("2026-10-17 09:10:00", Tz::UTC),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_timestamp("2026-10-17", Tz::UTC),
+            Some(Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap())
+        );
+        assert_eq!(parse_timestamp("acum 5 minute", Tz::UTC), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_in_timezone() {
+        let bucharest = chrono_tz::Europe::Bucharest;
+
+        // Bucharest is at UTC+3 in the summer and at UTC+2 in the winter.
+        assert_eq!(
+            parse_timestamp("2026-10-17 12:10:00", bucharest),
+            Some(Utc.with_ymd_and_hms(2026, 10, 17, 9, 10, 0).unwrap())
+        );
+        assert_eq!(
+            parse_timestamp("2026-12-17", bucharest),
+            Some(Utc.with_ymd_and_hms(2026, 12, 16, 22, 0, 0).unwrap())
+        );
+        // The timezone of the value wins.
+        assert_eq!(
+            parse_timestamp("2026-10-17T09:10:00Z", bucharest),
+            Some(Utc.with_ymd_and_hms(2026, 10, 17, 9, 10, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        let base = "https://some-news.ro/section/index.html";
+
+        assert_eq!(
+            resolve_url(base, "https://other.ro/a"),
+            Some(String::from("https://other.ro/a"))
+        );
+        assert_eq!(
+            resolve_url(base, "/article"),
+            Some(String::from("https://some-news.ro/article"))
+        );
+        assert_eq!(
+            resolve_url(base, "article?id=1"),
+            Some(String::from("https://some-news.ro/section/article?id=1"))
+        );
+        assert_eq!(
+            resolve_url(base, "//cdn.some-news.ro/image.jpg"),
+            Some(String::from("https://cdn.some-news.ro/image.jpg"))
+        );
+        assert_eq!(resolve_url(base, "javascript:void(0)"), None);
+        assert_eq!(resolve_url(base, "data:image/gif;base64,R0lGOD"), None);
+        assert_eq!(resolve_url(base, "mailto:news@some-news.ro"), None);
+        assert_eq!(resolve_url(base, "  "), None);
+        assert_eq!(
+            resolve_url("not a url", "https://some-news.ro/a"),
+            Some(String::from("https://some-news.ro/a"))
+        );
+        assert_eq!(resolve_url("not a url", "/a"), None);
+    }
+
+    #[tokio::test]
+    async fn test_post_urls_are_resolved() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let mut test_scraper = TestScrapper::new(&mock_server);
+        test_scraper.posts = vec![
+            NewsPost {
+                image: Some(String::from("//cdn.some-news.ro/a.jpg")),
+                title: Some(String::from("Relative")),
+                link: Some(String::from("/article/a")),
+                ..Default::default()
+            },
+            NewsPost {
+                image: Some(String::from("data:image/gif;base64,R0lGOD")),
+                title: Some(String::from("Not http")),
+                link: Some(String::from("javascript:void(0)")),
+                ..Default::default()
+            },
+        ];
+        let default_engine = WebScrapperEngine::default();
+
+        // Test
+        let posts = default_engine
+            .get_posts(&test_scraper)
+            .await?
+            .unwrap_or_default();
+
+        // Assert
+        assert_eq!(
+            posts[0].link,
+            Some(format!("{}/article/a", mock_server.uri()))
+        );
+        assert_eq!(
+            posts[0].image,
+            Some(String::from("http://cdn.some-news.ro/a.jpg"))
+        );
+        assert_eq!(posts[1].link, None);
+        assert_eq!(posts[1].image, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_conditional_request_not_modified() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .and(header("If-None-Match", "\"v1\""))
+            .and(header_exists("If-Modified-Since"))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .insert_header("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let test_scraper = TestScrapper::new(&mock_server);
+        let default_engine = WebScrapperEngine::default();
+
+        // Test
+        let first = default_engine.get_posts(&test_scraper).await?;
+        let second = default_engine.get_posts(&test_scraper).await?;
+
+        // Assert
+        assert_eq!(first, Some(vec![]));
+        assert_eq!(second, None);
+        mock_server.verify().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_conditional_request_without_validators() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let test_scraper = TestScrapper::new(&mock_server);
+        let default_engine = WebScrapperEngine::default();
+
+        // Test
+        let first = default_engine.get_posts(&test_scraper).await?;
+        let second = default_engine.get_posts(&test_scraper).await?;
+
+        // Assert
+        assert_eq!(first, Some(vec![]));
+        assert_eq!(second, Some(vec![]));
+        let requests = mock_server.received_requests().await.unwrap_or_default();
+        assert!(
+            requests
+                .iter()
+                .all(|r| !r.headers.contains_key("If-None-Match"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_robots_txt_disallowed_page_is_skipped() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /testing\n"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let test_scraper = TestScrapper::new(&mock_server);
+        let default_engine = WebScrapperEngine::default();
+
+        // Test
+        let first = default_engine.get_posts(&test_scraper).await;
+        let second = default_engine.get_posts(&test_scraper).await;
+
+        // Assert
+        assert!(first.is_err_and(|e| e.is::<FetchSkipped>()));
+        assert!(second.is_err_and(|e| e.is::<FetchSkipped>()));
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_robots_txt_is_a_failure() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let test_scraper = TestScrapper::new(&mock_server);
+        let default_engine = WebScrapperEngine::default();
+
+        // Test
+        let first = default_engine.get_posts(&test_scraper).await;
+        let second = default_engine.get_posts(&test_scraper).await;
+
+        // Assert
+        assert!(first.is_err_and(|e| !e.is::<FetchSkipped>()));
+        assert!(second.is_err_and(|e| !e.is::<FetchSkipped>()));
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_slow_robots_txt_does_not_block_other_origins() -> Result<(), anyhow::Error> {
+        // Setup
+        let slow_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(ResponseTemplate::new(404).set_delay(Duration::from_secs(3)))
+            .mount(&slow_server)
+            .await;
+        let fast_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&fast_server)
+            .await;
+
+        let engine = Arc::new(WebScrapperEngine::default());
+        let slow_engine = engine.clone();
+        let slow_url = format!("{}/article", slow_server.uri());
+        let slow = tokio::spawn(async move {
+            slow_engine
+                .get_page(&slow_url, Duration::from_secs(10))
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let start = Instant::now();
+
+        // Test
+        engine
+            .get_page(
+                &format!("{}/article", fast_server.uri()),
+                Duration::from_secs(5),
+            )
+            .await?;
+
+        // Assert
+        assert!(start.elapsed() < Duration::from_secs(2));
+        slow.abort();
+        fast_server.verify().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_robots_txt_is_ignored_when_disabled() {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /\n"),
+            )
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/testing"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let test_scraper = TestScrapper::new(&mock_server);
+        let engine = WebScrapperEngine::default().with_robots_txt(false);
+
+        // Test
+        let result = engine.get_posts(&test_scraper).await;
+
+        // Assert
+        assert!(result.is_ok());
+        mock_server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_crawl_delay_is_honoured() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nCrawl-delay: 0.2\n"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let engine = WebScrapperEngine::default().with_min_host_delay(Duration::from_millis(50));
+        let url = format!("{}/article", mock_server.uri());
+        let start = Instant::now();
+
+        // Test
+        for _ in 0..3 {
+            engine.get_page(&url, Duration::from_secs(5)).await?;
+        }
+
+        // Assert
+        assert!(start.elapsed() >= Duration::from_millis(400));
+        mock_server.verify().await;
+        Ok(())
+    }
+
+    /// A paginated page whose body lists one link per line, `next:` lines link to the next page.
+    struct PaginatedTestScrapper<'a> {
+        mock_server: &'a MockServer,
+    }
+
+    impl<'a> ScrapableWebPage for PaginatedTestScrapper<'a> {
+        fn get_url(&self) -> String {
+            format!("{}/politics", self.mock_server.uri())
+        }
+
+        fn get_posts(&self, html: String) -> Result<Vec<NewsPost>, Error> {
+            Ok(html
+                .lines()
+                .filter(|l| !l.starts_with("next:"))
+                .map(|l| NewsPost {
+                    title: Some(l.to_string()),
+                    link: Some(l.to_string()),
+                    ..Default::default()
+                })
+                .collect())
+        }
+
+        fn get_urls(&self) -> Vec<String> {
+            vec![self.get_url(), format!("{}/tech", self.mock_server.uri())]
+        }
+
+        fn get_next_page_url(&self, _url: &str, html: &str) -> Option<String> {
+            html.lines()
+                .find_map(|l| l.strip_prefix("next:"))
+                .map(|l| l.to_string())
+        }
+
+        fn get_max_depth(&self) -> usize {
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pages_are_merged_and_deduplicated() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        let pages = [
+            ("/politics", "/vote\n/budget\nnext:/politics/2", 1),
+            ("/politics/2", "/pensions\nnext:/politics/3", 1),
+            ("/politics/3", "/too-deep", 0),
+            ("/tech", "/budget\n/phones\nnext:/tech/2", 1),
+            ("/tech/2", "", 1),
+        ];
+        for (page, body, expected) in pages {
+            let response = if page == "/tech/2" {
+                ResponseTemplate::new(404)
+            } else {
+                ResponseTemplate::new(200).set_body_string(body)
+            };
+            Mock::given(method("GET"))
+                .and(path(page))
+                .respond_with(response)
+                .expect(expected)
+                .mount(&mock_server)
+                .await;
+        }
+        let test_scraper = PaginatedTestScrapper {
+            mock_server: &mock_server,
+        };
+        let engine = WebScrapperEngine::default().with_robots_txt(false);
+
+        // Test
+        let posts = engine.get_posts(&test_scraper).await?.unwrap_or_default();
+
+        // Assert
+        let links: Vec<String> = posts.into_iter().filter_map(|p| p.link).collect();
+        let uri = mock_server.uri();
+        assert_eq!(
+            links,
+            vec![
+                format!("{uri}/vote"),
+                format!("{uri}/budget"),
+                format!("{uri}/pensions"),
+                format!("{uri}/phones"),
+            ]
+        );
+        mock_server.verify().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pages_are_not_requested_conditionally() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header_exists("If-None-Match"))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        for (page, body) in [("/politics", "/vote"), ("/tech", "/phones")] {
+            Mock::given(method("GET"))
+                .and(path(page))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("ETag", "\"v1\"")
+                        .set_body_string(body),
+                )
+                .expect(2)
+                .mount(&mock_server)
+                .await;
+        }
+        let test_scraper = PaginatedTestScrapper {
+            mock_server: &mock_server,
+        };
+        let engine = WebScrapperEngine::default().with_robots_txt(false);
+
+        // Test
+        let first = engine.get_posts(&test_scraper).await?;
+        let second = engine.get_posts(&test_scraper).await?;
 
         // Assert
+        assert_eq!(first.map(|p| p.len()), Some(2));
+        assert_eq!(second.map(|p| p.len()), Some(2));
         mock_server.verify().await;
+        Ok(())
     }
 }
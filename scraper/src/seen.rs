@@ -0,0 +1,183 @@
+use crate::dedup::canonicalize_url;
+use infrastructure::RedisService;
+use log::warn;
+use post::NewsPost;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// The time in seconds the links seen on a source's previous run are kept in Redis.
+const SEEN_LINKS_TTL: u64 = 60 * 60 * 24 * 7;
+
+/// Returns the link identifying the post across runs, its canonical URL when it has one.
+pub fn seen_link(post: &NewsPost) -> Option<String> {
+    post.link
+        .as_deref()
+        .map(|link| canonicalize_url(link).unwrap_or_else(|| link.to_string()))
+}
+
+/// How the posts of a source changed since its previous run.
+#[derive(Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct LinkChanges {
+    /// The posts which weren't seen on the previous run, posts without a link are always new.
+    pub new: usize,
+    /// The posts which were already seen on the previous run.
+    pub unchanged: usize,
+    /// The posts of the previous run which are gone.
+    pub gone: usize,
+}
+
+impl LinkChanges {
+    /// Compares the links of the current run's posts with the links seen on the previous run.
+    pub fn between(previous: &HashSet<String>, posts: &[NewsPost]) -> Self {
+        let current: Vec<Option<String>> = posts.iter().map(seen_link).collect();
+        let unchanged = current
+            .iter()
+            .filter(|link| link.as_ref().is_some_and(|l| previous.contains(l)))
+            .count();
+        let current: HashSet<&String> = current.iter().flatten().collect();
+        LinkChanges {
+            new: posts.len() - unchanged,
+            unchanged,
+            gone: previous.iter().filter(|l| !current.contains(l)).count(),
+        }
+    }
+}
+
+/// Remembers the links of the posts seen on the previous run of each source.
+#[derive(Default)]
+pub struct SeenLinks {
+    sources: Mutex<HashMap<String, HashSet<String>>>,
+    store: Option<RedisService>,
+}
+
+impl SeenLinks {
+    /// Keeps the links in Redis so they survive restarts.
+    pub fn with_store(mut self, redis_service: RedisService) -> Self {
+        self.store = Some(redis_service);
+        self
+    }
+
+    /// Returns the Redis key holding the links seen on the source's previous run.
+    pub fn seen_links_key(source: &str) -> String {
+        format!("scraper:seen-links:{source}")
+    }
+
+    /// Returns the links seen on the source's previous run.
+    pub async fn previous(&self, source: &str) -> HashSet<String> {
+        let cached = self.sources.lock().unwrap().get(source).cloned();
+        if let Some(links) = cached {
+            return links;
+        }
+
+        let Some(mut store) = self.store.clone() else {
+            return HashSet::new();
+        };
+        match store
+            .get_value::<HashSet<String>>(&Self::seen_links_key(source))
+            .await
+        {
+            Ok(links) => links.unwrap_or_default(),
+            Err(err) => {
+                warn!("Failed to load the seen links of source {source}: {err}");
+                HashSet::new()
+            }
+        }
+    }
+
+    /// Remembers the links seen on the source's run, replacing the previous ones.
+    pub async fn remember(&self, source: &str, links: HashSet<String>) {
+        self.sources
+            .lock()
+            .unwrap()
+            .insert(source.to_string(), links.clone());
+        self.save(source, &links).await;
+    }
+
+    /// Forgets the link on every source, e.g. when its post failed to publish, so it's new on the next run.
+    pub async fn forget(&self, link: &str) {
+        let changed: Vec<(String, HashSet<String>)> = self
+            .sources
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter_map(|(source, links)| {
+                links.remove(link).then(|| (source.clone(), links.clone()))
+            })
+            .collect();
+        for (source, links) in changed {
+            self.save(&source, &links).await;
+        }
+    }
+
+    /// Saves the links seen on the source's run to the store.
+    async fn save(&self, source: &str, links: &HashSet<String>) {
+        if let Some(mut store) = self.store.clone()
+            && let Err(err) = store
+                .set_value(&Self::seen_links_key(source), links, Some(SEEN_LINKS_TTL))
+                .await
+        {
+            warn!("Failed to save the seen links of source {source}: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(link: Option<&str>) -> NewsPost {
+        NewsPost {
+            title: Some(String::from("Titlu")),
+            link: link.map(|l| l.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_link_changes() {
+        let previous = HashSet::from([
+            String::from("https://some-news.ro/1"),
+            String::from("https://some-news.ro/2"),
+            String::from("https://some-news.ro/3"),
+        ]);
+        let posts = vec![
+            post(Some("https://www.some-news.ro/1?utm_source=rss")),
+            post(Some("https://some-news.ro/2")),
+            post(Some("https://some-news.ro/4")),
+            post(None),
+        ];
+
+        assert_eq!(
+            LinkChanges::between(&previous, &posts),
+            LinkChanges {
+                new: 2,
+                unchanged: 2,
+                gone: 1
+            }
+        );
+        assert_eq!(
+            LinkChanges::between(&HashSet::new(), &posts),
+            LinkChanges {
+                new: 4,
+                unchanged: 0,
+                gone: 0
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_seen_links_in_memory() {
+        let seen_links = SeenLinks::default();
+        let links = HashSet::from([String::from("https://some-news.ro/1")]);
+
+        assert!(seen_links.previous("hotnews").await.is_empty());
+        seen_links.remember("hotnews", links.clone()).await;
+
+        assert_eq!(seen_links.previous("hotnews").await, links);
+        assert!(seen_links.previous("g4media").await.is_empty());
+
+        seen_links.forget("https://some-news.ro/1").await;
+        assert!(seen_links.previous("hotnews").await.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::seen::LinkChanges;
+use chrono::{DateTime, Utc};
+use post::NewsPost;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// How the last run of a source ended.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum RunStatus {
+    /// The posts were scraped.
+    Succeeded,
+    /// The source has not changed since its previous run.
+    NotModified,
+    /// The source may not be fetched, e.g. it's disallowed by its robots.txt.
+    Skipped(String),
+    /// The scrape failed or timed out.
+    Failed(String),
+}
+
+/// A post produced by the last run of a source.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RunPost {
+    #[serde(flatten)]
+    pub post: NewsPost,
+    /// Why the post was not sent for publishing, `None` if it was sent.
+    pub dropped: Option<String>,
+}
+
+/// The runtime status of a source.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SourceStatus {
+    pub name: String,
+    /// Disabled sources are not scraped until they are enabled again.
+    pub enabled: bool,
+    pub running: bool,
+    /// When the last run finished.
+    pub last_run: Option<DateTime<Utc>>,
+    pub last_status: Option<RunStatus>,
+    /// The number of posts found by the last successful run.
+    pub found: usize,
+    /// The number of posts sent for publishing by the last successful run.
+    pub sent: usize,
+    /// How the posts of the last successful run changed since the run before it.
+    pub changes: Option<LinkChanges>,
+    /// The posts of the last successful run.
+    #[serde(skip)]
+    pub posts: Vec<RunPost>,
+}
+
+impl SourceStatus {
+    fn new(name: &str) -> Self {
+        SourceStatus {
+            name: name.to_string(),
+            enabled: true,
+            running: false,
+            last_run: None,
+            last_status: None,
+            found: 0,
+            sent: 0,
+            changes: None,
+            posts: vec![],
+        }
+    }
+}
+
+/// Keeps the runtime status of every source, shared by the scrape jobs and the admin API.
+#[derive(Debug, Default)]
+pub struct SourceStatuses {
+    statuses: Mutex<BTreeMap<String, SourceStatus>>,
+}
+
+impl SourceStatuses {
+    /// Creates the statuses of the given sources, which are all enabled.
+    pub fn new<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        SourceStatuses {
+            statuses: Mutex::new(
+                names
+                    .into_iter()
+                    .map(|name| (name.to_string(), SourceStatus::new(name)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the statuses of all the sources, sorted by name.
+    pub fn list(&self) -> Vec<SourceStatus> {
+        self.statuses.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Returns the status of the source, `None` if there's no such source.
+    pub fn get(&self, name: &str) -> Option<SourceStatus> {
+        self.statuses.lock().unwrap().get(name).cloned()
+    }
+
+    /// Returns true if the source is enabled, unknown sources are enabled.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.statuses
+            .lock()
+            .unwrap()
+            .get(name)
+            .is_none_or(|s| s.enabled)
+    }
+
+    /// Enables or disables the source, returns its new status or `None` if there's no such source.
+    pub fn set_enabled(&self, name: &str, enabled: bool) -> Option<SourceStatus> {
+        let mut statuses = self.statuses.lock().unwrap();
+        let status = statuses.get_mut(name)?;
+        status.enabled = enabled;
+        Some(status.clone())
+    }
+
+    /// Marks the source as running.
+    pub fn start_run(&self, name: &str) {
+        let mut statuses = self.statuses.lock().unwrap();
+        let status = statuses
+            .entry(name.to_string())
+            .or_insert_with(|| SourceStatus::new(name));
+        status.running = true;
+    }
+
+    /// Records the end of the source's run, the counts and posts are only replaced by a successful run.
+    pub fn finish_run(
+        &self,
+        name: &str,
+        run_status: RunStatus,
+        posts: Option<Vec<RunPost>>,
+        changes: Option<LinkChanges>,
+    ) {
+        let mut statuses = self.statuses.lock().unwrap();
+        let status = statuses
+            .entry(name.to_string())
+            .or_insert_with(|| SourceStatus::new(name));
+        status.running = false;
+        status.last_run = Some(Utc::now());
+        status.last_status = Some(run_status);
+        if let Some(posts) = posts {
+            status.found = posts.len();
+            status.sent = posts.iter().filter(|p| p.dropped.is_none()).count();
+            status.posts = posts;
+            status.changes = changes;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_post(title: &str, dropped: Option<&str>) -> RunPost {
+        RunPost {
+            post: NewsPost {
+                title: Some(title.to_string()),
+                ..Default::default()
+            },
+            dropped: dropped.map(|d| d.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_source_statuses_runs() {
+        let statuses = SourceStatuses::new(["hotnews", "g4media"]);
+
+        statuses.start_run("hotnews");
+        assert!(statuses.get("hotnews").unwrap().running);
+
+        statuses.finish_run(
+            "hotnews",
+            RunStatus::Succeeded,
+            Some(vec![
+                run_post("First", None),
+                run_post("Second", Some("stale")),
+            ]),
+            Some(LinkChanges {
+                new: 1,
+                unchanged: 1,
+                gone: 3,
+            }),
+        );
+        statuses.finish_run(
+            "hotnews",
+            RunStatus::Failed(String::from("timed out")),
+            None,
+            None,
+        );
+
+        let status = statuses.get("hotnews").unwrap();
+        assert!(!status.running);
+        assert!(status.last_run.is_some());
+        assert_eq!(
+            status.last_status,
+            Some(RunStatus::Failed(String::from("timed out")))
+        );
+        assert_eq!((status.found, status.sent), (2, 1));
+        assert_eq!(status.changes.map(|c| c.gone), Some(3));
+        assert_eq!(status.posts.len(), 2);
+        assert_eq!(
+            statuses
+                .list()
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["g4media", "hotnews"]
+        );
+    }
+
+    #[test]
+    fn test_source_statuses_enable_disable() {
+        let statuses = SourceStatuses::new(["hotnews"]);
+
+        assert!(statuses.set_enabled("hotnews", false).is_some());
+        assert!(!statuses.is_enabled("hotnews"));
+        assert!(statuses.set_enabled("hotnews", true).is_some());
+        assert!(statuses.is_enabled("hotnews"));
+        assert!(statuses.set_enabled("missing", false).is_none());
+    }
+}
@@ -1,5 +1,13 @@
+pub mod feed;
 pub mod gfourmedia;
 pub mod hotnews;
+pub mod paginated;
+pub mod selectors;
+pub mod wordpress;
 
+pub use feed::Feed;
 pub use gfourmedia::GFourMedia;
 pub use hotnews::HotNews;
+pub use paginated::PaginatedWebPage;
+pub use selectors::{SelectorWebPage, SelectorsConfig};
+pub use wordpress::WordPress;
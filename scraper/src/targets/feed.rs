@@ -0,0 +1,279 @@
+use crate::language::language_code;
+use crate::scraper::ScrapableWebPage;
+use feed_rs::model::Entry;
+use post::NewsPost;
+use std::string::String;
+
+#[derive(Debug)]
+/// RSS 2.0 / Atom feed scraper
+pub struct Feed {
+    url: String,
+    default_author: String,
+}
+
+impl Feed {
+    /// Creates a new feed source for the given feed URL.
+    pub fn new(url: &str, default_author: &str) -> Self {
+        Feed {
+            url: url.to_string(),
+            default_author: default_author.to_string(),
+        }
+    }
+
+    /// Returns the link of the entry, preferring the alternate link over the others.
+    fn entry_link(entry: &Entry) -> Option<String> {
+        entry
+            .links
+            .iter()
+            .find(|l| l.rel.as_deref().is_none_or(|rel| rel == "alternate"))
+            .or_else(|| entry.links.first())
+            .map(|l| l.href.clone())
+    }
+
+    /// Returns the first image found in the entry's media, enclosures or thumbnails.
+    fn entry_image(entry: &Entry) -> Option<String> {
+        let media_image = entry.media.iter().find_map(|media| {
+            media
+                .content
+                .iter()
+                .filter(|c| {
+                    c.content_type
+                        .as_ref()
+                        .is_none_or(|t| t.to_string().starts_with("image/"))
+                })
+                .find_map(|c| c.url.as_ref().map(|u| u.to_string()))
+                .or_else(|| media.thumbnails.first().map(|t| t.image.uri.clone()))
+        });
+
+        // Atom feeds may declare images as enclosure links.
+        media_image.or_else(|| {
+            entry
+                .links
+                .iter()
+                .find(|l| {
+                    l.rel.as_deref() == Some("enclosure")
+                        && l.media_type
+                            .as_deref()
+                            .is_some_and(|t| t.starts_with("image/"))
+                })
+                .map(|l| l.href.clone())
+        })
+    }
+}
+
+impl ScrapableWebPage for Feed {
+    fn get_url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Follows the feed's `rel="next"` link, see RFC 5005.
+    fn get_next_page_url(&self, _url: &str, html: &str) -> Option<String> {
+        let feed = feed_rs::parser::parse(html.as_bytes()).ok()?;
+        feed.links
+            .into_iter()
+            .find(|l| l.rel.as_deref() == Some("next"))
+            .map(|l| l.href)
+    }
+
+    fn get_posts(&self, html: String) -> Result<Vec<NewsPost>, anyhow::Error> {
+        let feed = feed_rs::parser::parse(html.as_bytes())?;
+        let language = feed.language.as_deref().and_then(language_code);
+
+        let mut posts: Vec<NewsPost> = vec![];
+
+        for entry in feed.entries {
+            let authors: Vec<String> = entry
+                .authors
+                .iter()
+                .map(|a| a.name.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+
+            let news_post = NewsPost {
+                image: Feed::entry_image(&entry),
+                title: entry
+                    .title
+                    .as_ref()
+                    .map(|t| t.content.trim().to_string())
+                    .filter(|t| !t.is_empty()),
+                summary: entry
+                    .summary
+                    .as_ref()
+                    .map(|s| s.content.trim().to_string())
+                    .filter(|s| !s.is_empty()),
+                link: Feed::entry_link(&entry),
+                author: if !authors.is_empty() {
+                    Some(authors.join(", "))
+                } else {
+                    Some(self.default_author.clone())
+                },
+                published_at: entry.published.or(entry.updated),
+                language: language.clone(),
+                ..Default::default()
+            };
+
+            posts.push(news_post);
+        }
+
+        Ok(posts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WebScrapperEngine;
+    use crate::fixtures::{assert_golden, refresh_snapshot};
+    use chrono::{TimeZone, Utc};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const RSS_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Some News</title>
+    <link>https://some-news.ro</link>
+    <description>News</description>
+    <item>
+      <title>First article</title>
+      <link>https://some-news.ro/first</link>
+      <description>The first summary</description>
+      <dc:creator>Jane Doe</dc:creator>
+      <enclosure url="https://some-news.ro/first.jpg" length="100" type="image/jpeg"/>
+    </item>
+    <item>
+      <title>Second article</title>
+      <link>https://some-news.ro/second</link>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM_FEED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:media="http://search.yahoo.com/mrss/">
+  <title>Some News</title>
+  <id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>
+  <updated>2025-01-01T18:30:02Z</updated>
+  <link rel="next" href="https://some-news.ro/atom?page=2"/>
+  <entry>
+    <title>Atom article</title>
+    <link rel="alternate" href="https://some-news.ro/atom"/>
+    <id>urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a</id>
+    <updated>2025-01-01T18:30:02Z</updated>
+    <summary>The atom summary</summary>
+    <author><name>John Doe</name></author>
+    <media:content url="https://some-news.ro/atom.png" medium="image" type="image/png"/>
+  </entry>
+</feed>"#;
+
+    fn g4media_feed() -> Feed {
+        Feed::new("https://www.g4media.ro/feed", "G4Media")
+    }
+
+    #[test]
+    fn test_get_posts_golden() -> Result<(), anyhow::Error> {
+        assert_golden(&g4media_feed(), "feed.xml", "feed.json")
+    }
+
+    #[tokio::test]
+    #[ignore = "fetches the live site, run with `make refresh-scraper-fixtures`"]
+    async fn refresh_fixture() -> Result<(), anyhow::Error> {
+        refresh_snapshot(&g4media_feed(), "feed.xml").await
+    }
+
+    #[test]
+    fn test_rss_feed_get_posts() -> Result<(), anyhow::Error> {
+        let feed = Feed::new("https://some-news.ro/feed", "Some News");
+
+        let posts = feed.get_posts(RSS_FEED.to_string())?;
+
+        assert_eq!(
+            posts,
+            vec![
+                NewsPost {
+                    image: Some(String::from("https://some-news.ro/first.jpg")),
+                    title: Some(String::from("First article")),
+                    summary: Some(String::from("The first summary")),
+                    link: Some(String::from("https://some-news.ro/first")),
+                    author: Some(String::from("Jane Doe")),
+                    ..Default::default()
+                },
+                NewsPost {
+                    title: Some(String::from("Second article")),
+                    link: Some(String::from("https://some-news.ro/second")),
+                    author: Some(String::from("Some News")),
+                    ..Default::default()
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_atom_feed_get_posts() -> Result<(), anyhow::Error> {
+        let feed = Feed::new("https://some-news.ro/atom.xml", "Some News");
+
+        let posts = feed.get_posts(ATOM_FEED.to_string())?;
+
+        assert_eq!(
+            posts,
+            vec![NewsPost {
+                image: Some(String::from("https://some-news.ro/atom.png")),
+                title: Some(String::from("Atom article")),
+                summary: Some(String::from("The atom summary")),
+                link: Some(String::from("https://some-news.ro/atom")),
+                author: Some(String::from("John Doe")),
+                published_at: Some(Utc.with_ymd_and_hms(2025, 1, 1, 18, 30, 2).unwrap()),
+                ..Default::default()
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_feed_next_page_url() {
+        let feed = Feed::new("https://some-news.ro/atom", "Some News");
+
+        assert_eq!(
+            feed.get_next_page_url("https://some-news.ro/atom", ATOM_FEED),
+            Some(String::from("https://some-news.ro/atom?page=2"))
+        );
+        assert_eq!(
+            feed.get_next_page_url("https://some-news.ro/feed", RSS_FEED),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_feed_returns_error() {
+        let feed = Feed::new("https://some-news.ro/feed", "Some News");
+
+        let result = feed.get_posts(String::from("<html><body>Not a feed</body></html>"));
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_feed_is_scraped_by_engine() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/feed"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(RSS_FEED))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let feed = Feed::new(&format!("{}/feed", mock_server.uri()), "Some News");
+
+        // Test
+        let posts = WebScrapperEngine::default()
+            .get_posts(&feed)
+            .await?
+            .unwrap_or_default();
+
+        // Assert
+        assert_eq!(posts.len(), 2);
+        mock_server.verify().await;
+        Ok(())
+    }
+}
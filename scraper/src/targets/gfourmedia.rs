@@ -1,5 +1,6 @@
-use crate::scraper::ScrapableWebPage;
+use crate::scraper::{ScrapableWebPage, parse_timestamp};
 use anyhow::anyhow;
+use chrono_tz::Europe;
 use post::NewsPost;
 use scraper::{Html, Selector};
 use std::string::String;
@@ -46,16 +47,12 @@ impl ScrapableWebPage for GFourMedia {
             .map_err(|_e| anyhow!("failed to make selector"))?;
         let post_excerpt_selector = Selector::parse(".article__excerpt")
             .map_err(|_e| anyhow!("failed to make selector"))?;
+        let post_time_selector =
+            Selector::parse("time[datetime]").map_err(|_e| anyhow!("failed to make selector"))?;
         let selected_posts = document.select(&posts_selector);
 
         for element in selected_posts {
-            let mut news_post = NewsPost {
-                image: None,
-                title: None,
-                summary: None,
-                link: None,
-                author: None,
-            };
+            let mut news_post = NewsPost::default();
 
             if let Some(post_title) = element.select(&post_title_selector).next() {
                 news_post.title = Some(post_title.inner_html().trim().to_string());
@@ -87,6 +84,13 @@ impl ScrapableWebPage for GFourMedia {
                 Some(self.default_author.clone())
             };
 
+            // The times without an offset are Romanian local times.
+            news_post.published_at = element
+                .select(&post_time_selector)
+                .next()
+                .and_then(|time| time.attr("datetime"))
+                .and_then(|time| parse_timestamp(time, Europe::Bucharest));
+
             // Extract summary/excerpt if present
             if let Some(excerpt_el) = element.select(&post_excerpt_selector).next() {
                 let excerpt_text: String = excerpt_el.text().collect::<Vec<_>>().join(" ");
@@ -107,16 +111,29 @@ impl ScrapableWebPage for GFourMedia {
 mod tests {
     use crate::GFourMedia;
     use crate::WebScrapperEngine;
+    use crate::fixtures::{assert_golden, refresh_snapshot};
+
+    #[test]
+    fn test_get_posts_golden() -> Result<(), anyhow::Error> {
+        assert_golden(&GFourMedia::default(), "gfourmedia.html", "gfourmedia.json")
+    }
+
+    #[tokio::test]
+    #[ignore = "fetches the live site, run with `make refresh-scraper-fixtures`"]
+    async fn refresh_fixture() -> Result<(), anyhow::Error> {
+        refresh_snapshot(&GFourMedia::default(), "gfourmedia.html").await
+    }
 
     #[tokio::test]
+    #[ignore = "fetches the live site"]
     async fn sanity_test() {
         let posts = WebScrapperEngine::default()
-            .get_posts(GFourMedia::default())
+            .get_posts(&GFourMedia::default())
             .await;
 
         assert!(posts.is_ok());
 
-        let posts = posts.unwrap();
+        let posts = posts.unwrap().unwrap_or_default();
 
         assert!(!posts.is_empty());
 
@@ -1,5 +1,6 @@
-use crate::scraper::ScrapableWebPage;
+use crate::scraper::{ScrapableWebPage, parse_timestamp};
 use anyhow::anyhow;
+use chrono_tz::Europe;
 use post::NewsPost;
 use scraper::{Html, Selector};
 use std::string::String;
@@ -36,16 +37,12 @@ impl ScrapableWebPage for HotNews {
             Selector::parse("figure > a > img").map_err(|_e| anyhow!("failed to make selector"))?;
         let post_title_selector = Selector::parse("div.entry-wrapper > h2.entry-title > a")
             .map_err(|_e| anyhow!("failed to make selector"))?;
+        let post_time_selector =
+            Selector::parse("time[datetime]").map_err(|_e| anyhow!("failed to make selector"))?;
         let selected_posts = document.select(&posts_selector);
 
         for element in selected_posts {
-            let mut news_post = NewsPost {
-                image: None,
-                title: None,
-                summary: None,
-                link: None,
-                author: None,
-            };
+            let mut news_post = NewsPost::default();
 
             if let Some(post_title) = element.select(&post_title_selector).next() {
                 news_post.title = Some(post_title.inner_html().trim().to_string());
@@ -60,6 +57,13 @@ impl ScrapableWebPage for HotNews {
                 news_post.image = Some(image_source.to_string());
             }
 
+            // The times without an offset are Romanian local times.
+            news_post.published_at = element
+                .select(&post_time_selector)
+                .next()
+                .and_then(|time| time.attr("datetime"))
+                .and_then(|time| parse_timestamp(time, Europe::Bucharest));
+
             news_post.author = Option::from(self.default_author.clone());
             news_post.summary = Option::from(String::from(""));
 
@@ -74,17 +78,29 @@ impl ScrapableWebPage for HotNews {
 mod tests {
     use crate::HotNews;
     use crate::WebScrapperEngine;
+    use crate::fixtures::{assert_golden, refresh_snapshot};
+
+    #[test]
+    fn test_get_posts_golden() -> Result<(), anyhow::Error> {
+        assert_golden(&HotNews::default(), "hotnews.html", "hotnews.json")
+    }
+
+    #[tokio::test]
+    #[ignore = "fetches the live site, run with `make refresh-scraper-fixtures`"]
+    async fn refresh_fixture() -> Result<(), anyhow::Error> {
+        refresh_snapshot(&HotNews::default(), "hotnews.html").await
+    }
 
     #[tokio::test]
-    #[should_panic]
+    #[ignore = "fetches the live site"]
     async fn sanity_test() {
         let posts = WebScrapperEngine::default()
-            .get_posts(HotNews::default())
+            .get_posts(&HotNews::default())
             .await;
 
         assert!(posts.is_ok());
 
-        let posts = posts.unwrap();
+        let posts = posts.unwrap().unwrap_or_default();
 
         assert!(!posts.is_empty());
 
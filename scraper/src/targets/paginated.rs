@@ -0,0 +1,45 @@
+use crate::scraper::ScrapableWebPage;
+use post::NewsPost;
+use std::sync::Arc;
+
+/// Scrapes additional pages with the same target, e.g. category pages, and follows their next pages.
+pub struct PaginatedWebPage {
+    page: Arc<dyn ScrapableWebPage>,
+    extra_urls: Vec<String>,
+    max_depth: usize,
+}
+
+impl PaginatedWebPage {
+    /// Wraps the target, which is scraped from its own URL and the extra URLs.
+    pub fn new(page: Arc<dyn ScrapableWebPage>, extra_urls: &[String], max_depth: usize) -> Self {
+        PaginatedWebPage {
+            page,
+            extra_urls: extra_urls.to_vec(),
+            max_depth,
+        }
+    }
+}
+
+impl ScrapableWebPage for PaginatedWebPage {
+    fn get_url(&self) -> String {
+        self.page.get_url()
+    }
+
+    fn get_posts(&self, html: String) -> Result<Vec<NewsPost>, anyhow::Error> {
+        self.page.get_posts(html)
+    }
+
+    fn get_urls(&self) -> Vec<String> {
+        let mut urls = self.page.get_urls();
+        urls.extend(self.extra_urls.iter().cloned());
+        urls
+    }
+
+    fn get_next_page_url(&self, url: &str, html: &str) -> Option<String> {
+        self.page.get_next_page_url(url, html)
+    }
+
+    fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
@@ -0,0 +1,330 @@
+use crate::scraper::{ScrapableWebPage, parse_timestamp};
+use anyhow::anyhow;
+use chrono_tz::Tz;
+use post::NewsPost;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use std::string::String;
+
+/// The CSS selectors used to extract posts from a web page.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SelectorsConfig {
+    /// Selects the element containing a single post.
+    pub container: String,
+    /// Selects the title of the post, relative to the container.
+    pub title: String,
+    /// Selects the element holding the post's `href`. Defaults to the title element.
+    pub link: Option<String>,
+    /// Selects the image of the post, the `src` or `data-src` attribute is used.
+    pub image: Option<String>,
+    /// Selects the authors of the post, multiple authors are joined.
+    pub author: Option<String>,
+    /// Selects the summary/excerpt of the post.
+    pub excerpt: Option<String>,
+    /// Selects the publication time of the post, the `datetime` attribute or the text is used.
+    pub published_at: Option<String>,
+    /// Selects the link to the next page, relative to the page. Followed up to the source's `max_depth`.
+    pub next_page: Option<String>,
+    /// The timezone of the publication times without one, e.g. `Europe/Bucharest`. Defaults to UTC.
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// Parses a CSS selector.
+fn parse_selector(selector: &str) -> Result<Selector, anyhow::Error> {
+    Selector::parse(selector).map_err(|e| anyhow!("failed to make selector `{selector}`: {e}"))
+}
+
+/// Parses an optional CSS selector.
+fn parse_optional_selector(selector: &Option<String>) -> Result<Option<Selector>, anyhow::Error> {
+    selector.as_deref().map(parse_selector).transpose()
+}
+
+/// Returns the text of the element with the whitespace collapsed.
+fn element_text(element: &ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug)]
+/// Generic website scraper driven by CSS selectors
+pub struct SelectorWebPage {
+    url: String,
+    default_author: String,
+    container: Selector,
+    title: Selector,
+    link: Option<Selector>,
+    image: Option<Selector>,
+    author: Option<Selector>,
+    excerpt: Option<Selector>,
+    published_at: Option<Selector>,
+    next_page: Option<Selector>,
+    timezone: Tz,
+}
+
+impl SelectorWebPage {
+    /// Creates a new selector based web page, returns an error if any of the selectors or the timezone is invalid.
+    pub fn new(
+        url: &str,
+        default_author: &str,
+        selectors: &SelectorsConfig,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(SelectorWebPage {
+            url: url.to_string(),
+            default_author: default_author.to_string(),
+            container: parse_selector(&selectors.container)?,
+            title: parse_selector(&selectors.title)?,
+            link: parse_optional_selector(&selectors.link)?,
+            image: parse_optional_selector(&selectors.image)?,
+            author: parse_optional_selector(&selectors.author)?,
+            excerpt: parse_optional_selector(&selectors.excerpt)?,
+            published_at: parse_optional_selector(&selectors.published_at)?,
+            next_page: parse_optional_selector(&selectors.next_page)?,
+            timezone: match &selectors.timezone {
+                Some(timezone) => timezone
+                    .parse()
+                    .map_err(|e| anyhow!("invalid timezone `{timezone}`: {e}"))?,
+                None => Tz::UTC,
+            },
+        })
+    }
+}
+
+impl ScrapableWebPage for SelectorWebPage {
+    fn get_url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn get_next_page_url(&self, _url: &str, html: &str) -> Option<String> {
+        let next_page = self.next_page.as_ref()?;
+        Html::parse_document(html)
+            .select(next_page)
+            .find_map(|a| a.attr("href").map(|href| href.to_string()))
+    }
+
+    fn get_posts(&self, html: String) -> Result<Vec<NewsPost>, anyhow::Error> {
+        let document = Html::parse_document(&html);
+
+        let mut posts: Vec<NewsPost> = vec![];
+
+        for element in document.select(&self.container) {
+            let mut news_post = NewsPost::default();
+
+            let title_element = element.select(&self.title).next();
+            if let Some(post_title) = title_element {
+                let title = element_text(&post_title);
+                if !title.is_empty() {
+                    news_post.title = Some(title);
+                }
+            }
+
+            let link_element = match &self.link {
+                Some(link_selector) => element.select(link_selector).next(),
+                None => title_element,
+            };
+            if let Some(href) = link_element.and_then(|l| l.attr("href")) {
+                news_post.link = Some(href.to_owned());
+            }
+
+            if let Some(image_selector) = &self.image
+                && let Some(selected_image) = element.select(image_selector).next()
+                && let Some(image_source) = selected_image
+                    .attr("src")
+                    .or_else(|| selected_image.attr("data-src"))
+            {
+                news_post.image = Some(image_source.to_string());
+            }
+
+            let authors: Vec<String> = match &self.author {
+                Some(author_selector) => element
+                    .select(author_selector)
+                    .map(|a| element_text(&a))
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+                None => vec![],
+            };
+            news_post.author = if !authors.is_empty() {
+                Some(authors.join(", "))
+            } else {
+                Some(self.default_author.clone())
+            };
+
+            if let Some(excerpt_selector) = &self.excerpt
+                && let Some(excerpt_el) = element.select(excerpt_selector).next()
+            {
+                let excerpt = element_text(&excerpt_el);
+                if !excerpt.is_empty() {
+                    news_post.summary = Some(excerpt);
+                }
+            }
+
+            if let Some(time_selector) = &self.published_at
+                && let Some(time_el) = element.select(time_selector).next()
+            {
+                news_post.published_at = time_el
+                    .attr("datetime")
+                    .and_then(|time| parse_timestamp(time, self.timezone))
+                    .or_else(|| parse_timestamp(&element_text(&time_el), self.timezone));
+            }
+
+            posts.push(news_post);
+        }
+
+        Ok(posts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::assert_golden;
+    use chrono::{TimeZone, Utc};
+
+    const HTML: &str = r#"
+<html><body>
+  <div class="article">
+    <figure><img data-src="https://some-news.ro/1.jpg"/></figure>
+    <h2 class="title"><a href="https://some-news.ro/1">First <em>article</em></a></h2>
+    <div class="meta"><a rel="author">Jane</a><a rel="author">John</a><time datetime="2026-10-17T12:10:00+03:00">acum o oră</time></div>
+    <p class="excerpt"> The excerpt </p>
+  </div>
+  <div class="article">
+    <h2 class="title">Second article</h2>
+    <a class="more" href="https://some-news.ro/2">Read more</a>
+    <span class="date"> 2026-10-17 </span>
+  </div>
+  <nav><a class="next" href="/page/2">Next</a></nav>
+</body></html>"#;
+
+    fn selectors() -> SelectorsConfig {
+        SelectorsConfig {
+            container: String::from("div.article"),
+            title: String::from("h2.title"),
+            link: Some(String::from("h2.title > a, a.more")),
+            image: Some(String::from("figure img")),
+            author: Some(String::from(".meta a[rel='author']")),
+            excerpt: Some(String::from(".excerpt")),
+            published_at: Some(String::from("time, .date")),
+            next_page: Some(String::from("a.next")),
+            timezone: None,
+        }
+    }
+
+    #[test]
+    fn test_get_posts_golden() -> Result<(), anyhow::Error> {
+        // The same selectors as the g4media source from sources.toml.
+        let selectors = SelectorsConfig {
+            container: String::from("div.article"),
+            title: String::from("h2.article__title > a"),
+            link: None,
+            image: Some(String::from("figure picture img, .article__media img")),
+            author: Some(String::from(".article__eyebrow a[rel='author']")),
+            excerpt: Some(String::from(".article__excerpt")),
+            published_at: Some(String::from("time[datetime]")),
+            next_page: None,
+            timezone: Some(String::from("Europe/Bucharest")),
+        };
+        let page = SelectorWebPage::new("https://www.g4media.ro/", "G4Media", &selectors)?;
+
+        assert_golden(&page, "gfourmedia.html", "selectors.json")
+    }
+
+    #[test]
+    fn test_selector_web_page_get_posts() -> Result<(), anyhow::Error> {
+        let page = SelectorWebPage::new("https://some-news.ro", "Some News", &selectors())?;
+
+        let posts = page.get_posts(HTML.to_string())?;
+
+        assert_eq!(
+            posts,
+            vec![
+                NewsPost {
+                    image: Some(String::from("https://some-news.ro/1.jpg")),
+                    title: Some(String::from("First article")),
+                    summary: Some(String::from("The excerpt")),
+                    link: Some(String::from("https://some-news.ro/1")),
+                    author: Some(String::from("Jane, John")),
+                    published_at: Some(Utc.with_ymd_and_hms(2026, 10, 17, 9, 10, 0).unwrap()),
+                    ..Default::default()
+                },
+                NewsPost {
+                    title: Some(String::from("Second article")),
+                    link: Some(String::from("https://some-news.ro/2")),
+                    author: Some(String::from("Some News")),
+                    published_at: Some(Utc.with_ymd_and_hms(2026, 10, 17, 0, 0, 0).unwrap()),
+                    ..Default::default()
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_selector_web_page_link_defaults_to_title() -> Result<(), anyhow::Error> {
+        let mut selectors = selectors();
+        selectors.title = String::from("h2.title > a");
+        selectors.link = None;
+        let page = SelectorWebPage::new("https://some-news.ro", "Some News", &selectors)?;
+
+        let posts = page.get_posts(HTML.to_string())?;
+
+        assert_eq!(posts[0].link, Some(String::from("https://some-news.ro/1")));
+        assert_eq!(posts[1].link, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_selector_web_page_next_page_url() -> Result<(), anyhow::Error> {
+        let mut selectors = selectors();
+        let page = SelectorWebPage::new("https://some-news.ro", "Some News", &selectors)?;
+
+        assert_eq!(
+            page.get_next_page_url("https://some-news.ro", HTML),
+            Some(String::from("/page/2"))
+        );
+
+        selectors.next_page = None;
+        let page = SelectorWebPage::new("https://some-news.ro", "Some News", &selectors)?;
+
+        assert_eq!(page.get_next_page_url("https://some-news.ro", HTML), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_selector_web_page_timezone() -> Result<(), anyhow::Error> {
+        let mut selectors = selectors();
+        selectors.timezone = Some(String::from("Europe/Bucharest"));
+        let page = SelectorWebPage::new("https://some-news.ro", "Some News", &selectors)?;
+
+        let posts = page.get_posts(HTML.to_string())?;
+
+        // The first time has an offset, the second is a date in Bucharest.
+        assert_eq!(
+            posts[0].published_at,
+            Some(Utc.with_ymd_and_hms(2026, 10, 17, 9, 10, 0).unwrap())
+        );
+        assert_eq!(
+            posts[1].published_at,
+            Some(Utc.with_ymd_and_hms(2026, 10, 16, 21, 0, 0).unwrap())
+        );
+
+        selectors.timezone = Some(String::from("Mars/Olympus_Mons"));
+        assert!(SelectorWebPage::new("https://some-news.ro", "Some News", &selectors).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_selector_web_page_invalid_selector() {
+        let mut selectors = selectors();
+        selectors.container = String::from("div..article");
+
+        let result = SelectorWebPage::new("https://some-news.ro", "Some News", &selectors);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,304 @@
+use crate::scraper::{ScrapableWebPage, parse_timestamp};
+use chrono_tz::Tz;
+use post::NewsPost;
+use scraper::Html;
+use serde::Deserialize;
+use std::string::String;
+use url::Url;
+
+/// A rendered field from the WordPress REST API.
+#[derive(Deserialize, Debug, Default)]
+struct Rendered {
+    #[serde(default)]
+    rendered: String,
+}
+
+/// An embedded author, restricted authors come back as error objects without a name.
+#[derive(Deserialize, Debug)]
+struct EmbeddedAuthor {
+    name: Option<String>,
+}
+
+/// An embedded featured media, restricted media come back as error objects without a source.
+#[derive(Deserialize, Debug)]
+struct EmbeddedMedia {
+    source_url: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct Embedded {
+    #[serde(default)]
+    author: Vec<EmbeddedAuthor>,
+    #[serde(rename = "wp:featuredmedia", default)]
+    featured_media: Vec<EmbeddedMedia>,
+}
+
+/// Is a partial post from the /wp-json/wp/v2/posts endpoint.
+/// See: https://developer.wordpress.org/rest-api/reference/posts/
+#[derive(Deserialize, Debug)]
+struct WordPressPost {
+    link: Option<String>,
+    /// The publication time in UTC, without a timezone.
+    date_gmt: Option<String>,
+    #[serde(default)]
+    title: Rendered,
+    #[serde(default)]
+    excerpt: Rendered,
+    #[serde(rename = "_embedded", default)]
+    embedded: Embedded,
+}
+
+/// The number of posts returned by the API when the request has no `per_page`.
+const DEFAULT_PER_PAGE: usize = 10;
+
+/// Converts rendered HTML to plain text.
+fn rendered_to_text(rendered: &str) -> Option<String> {
+    let text = Html::parse_fragment(rendered)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() { None } else { Some(text) }
+}
+
+#[derive(Debug)]
+/// WordPress REST API scraper
+pub struct WordPress {
+    url: String,
+    default_author: String,
+}
+
+impl WordPress {
+    /// Creates a new WordPress source for the site at the given URL.
+    pub fn new(url: &str, default_author: &str) -> Self {
+        WordPress {
+            url: url.trim_end_matches('/').to_string(),
+            default_author: default_author.to_string(),
+        }
+    }
+}
+
+impl ScrapableWebPage for WordPress {
+    fn get_url(&self) -> String {
+        format!("{}/wp-json/wp/v2/posts?_embed", self.url)
+    }
+
+    /// Requests the next `page` while the current one is full.
+    fn get_next_page_url(&self, url: &str, html: &str) -> Option<String> {
+        let mut url = Url::parse(url).ok()?;
+        let query_value = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+        };
+        let page = query_value("page").unwrap_or(1);
+        let per_page = query_value("per_page").unwrap_or(DEFAULT_PER_PAGE);
+
+        let posts: Vec<serde_json::Value> = serde_json::from_str(html).ok()?;
+        if posts.len() < per_page {
+            return None;
+        }
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| key != "page")
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(pairs)
+            .append_pair("page", &(page + 1).to_string());
+        Some(url.to_string())
+    }
+
+    fn get_posts(&self, html: String) -> Result<Vec<NewsPost>, anyhow::Error> {
+        let wordpress_posts: Vec<WordPressPost> = serde_json::from_str(&html)?;
+
+        let posts = wordpress_posts
+            .into_iter()
+            .map(|p| NewsPost {
+                image: p
+                    .embedded
+                    .featured_media
+                    .into_iter()
+                    .find_map(|m| m.source_url),
+                title: rendered_to_text(&p.title.rendered),
+                summary: rendered_to_text(&p.excerpt.rendered),
+                link: p.link,
+                author: p
+                    .embedded
+                    .author
+                    .into_iter()
+                    .find_map(|a| a.name.filter(|n| !n.trim().is_empty()))
+                    .or_else(|| Some(self.default_author.clone())),
+                published_at: p
+                    .date_gmt
+                    .as_deref()
+                    .and_then(|date| parse_timestamp(date, Tz::UTC)),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(posts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WebScrapperEngine;
+    use crate::fixtures::{assert_golden, refresh_snapshot};
+    use chrono::{TimeZone, Utc};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const POSTS: &str = r#"[
+  {
+    "id": 1,
+    "date_gmt": "2026-10-17T09:10:00",
+    "link": "https://some-news.ro/first",
+    "title": {"rendered": "First &#8222;article&#8221;"},
+    "excerpt": {"rendered": "<p>The first <em>summary</em> [&hellip;]</p>\n"},
+    "_embedded": {
+      "author": [{"id": 2, "name": "Jane Doe"}],
+      "wp:featuredmedia": [{"id": 3, "source_url": "https://some-news.ro/first.jpg"}]
+    }
+  },
+  {
+    "id": 2,
+    "link": "https://some-news.ro/second",
+    "title": {"rendered": "Second article"},
+    "excerpt": {"rendered": ""},
+    "_embedded": {
+      "author": [{"code": "rest_user_invalid_id", "message": "Invalid user ID."}],
+      "wp:featuredmedia": [{"code": "rest_forbidden", "message": "Forbidden."}]
+    }
+  },
+  {
+    "id": 3,
+    "link": "https://some-news.ro/third",
+    "title": {"rendered": "Third article"},
+    "excerpt": {"rendered": "<p>Third</p>"}
+  }
+]"#;
+
+    fn hotnews() -> WordPress {
+        WordPress::new("https://www.hotnews.ro", "HotNews")
+    }
+
+    #[test]
+    fn test_get_posts_golden() -> Result<(), anyhow::Error> {
+        assert_golden(&hotnews(), "wordpress.json", "wordpress.json")
+    }
+
+    #[tokio::test]
+    #[ignore = "fetches the live site, run with `make refresh-scraper-fixtures`"]
+    async fn refresh_fixture() -> Result<(), anyhow::Error> {
+        refresh_snapshot(&hotnews(), "wordpress.json").await
+    }
+
+    #[test]
+    fn test_wordpress_get_url() {
+        let wordpress = WordPress::new("https://some-news.ro/", "Some News");
+
+        assert_eq!(
+            wordpress.get_url(),
+            "https://some-news.ro/wp-json/wp/v2/posts?_embed"
+        );
+    }
+
+    #[test]
+    fn test_wordpress_next_page_url() {
+        let wordpress = WordPress::new("https://some-news.ro", "Some News");
+        let url = "https://some-news.ro/wp-json/wp/v2/posts?_embed&per_page=3";
+
+        let next_url = wordpress.get_next_page_url(url, POSTS);
+
+        assert_eq!(
+            next_url.as_deref(),
+            Some("https://some-news.ro/wp-json/wp/v2/posts?_embed=&per_page=3&page=2")
+        );
+        assert_eq!(
+            wordpress.get_next_page_url(next_url.as_deref().unwrap(), POSTS),
+            Some(String::from(
+                "https://some-news.ro/wp-json/wp/v2/posts?_embed=&per_page=3&page=3"
+            ))
+        );
+        assert_eq!(
+            wordpress.get_next_page_url(&wordpress.get_url(), POSTS),
+            None
+        );
+    }
+
+    #[test]
+    fn test_wordpress_get_posts() -> Result<(), anyhow::Error> {
+        let wordpress = WordPress::new("https://some-news.ro", "Some News");
+
+        let posts = wordpress.get_posts(POSTS.to_string())?;
+
+        assert_eq!(
+            posts,
+            vec![
+                NewsPost {
+                    image: Some(String::from("https://some-news.ro/first.jpg")),
+                    title: Some(String::from("First „article”")),
+                    summary: Some(String::from("The first summary […]")),
+                    link: Some(String::from("https://some-news.ro/first")),
+                    author: Some(String::from("Jane Doe")),
+                    published_at: Some(Utc.with_ymd_and_hms(2026, 10, 17, 9, 10, 0).unwrap()),
+                    ..Default::default()
+                },
+                NewsPost {
+                    title: Some(String::from("Second article")),
+                    link: Some(String::from("https://some-news.ro/second")),
+                    author: Some(String::from("Some News")),
+                    ..Default::default()
+                },
+                NewsPost {
+                    title: Some(String::from("Third article")),
+                    summary: Some(String::from("Third")),
+                    link: Some(String::from("https://some-news.ro/third")),
+                    author: Some(String::from("Some News")),
+                    ..Default::default()
+                }
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_wordpress_invalid_response_returns_error() {
+        let wordpress = WordPress::new("https://some-news.ro", "Some News");
+
+        let result = wordpress.get_posts(String::from("<html>Not the API</html>"));
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wordpress_is_scraped_by_engine() -> Result<(), anyhow::Error> {
+        // Setup
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wp-json/wp/v2/posts"))
+            .and(query_param("_embed", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(POSTS))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let wordpress = WordPress::new(&mock_server.uri(), "Some News");
+
+        // Test
+        let posts = WebScrapperEngine::default()
+            .get_posts(&wordpress)
+            .await?
+            .unwrap_or_default();
+
+        // Assert
+        assert_eq!(posts.len(), 3);
+        mock_server.verify().await;
+        Ok(())
+    }
+}